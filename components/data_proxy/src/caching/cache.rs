@@ -325,6 +325,9 @@ impl Cache {
         &self,
         temp_locations: Vec<(Object, ObjectLocation)>,
     ) -> Result<()> {
+        if temp_locations.is_empty() {
+            return Ok(());
+        }
         let Some(backend) = &self.backend else {
             bail!("No backend found")
         };
@@ -708,15 +711,14 @@ impl Cache {
     #[tracing::instrument(level = "trace", skip(self))]
     pub async fn remove_user(&self, user_id: DieselUlid) -> Result<()> {
         if let Some((u, v)) = self.users.remove(&user_id) {
-            for key in v.read().await.1.iter() {
+            let keys = v.read().await.1.clone();
+            for key in keys.iter() {
                 self.access_keys.remove(key.as_str());
-                if let Some(persistence) = self.persistence.read().await.as_ref() {
-                    AccessKeyPermissions::delete(key, persistence.get_client().await?.client())
-                        .await?;
-                }
             }
             if let Some(persistence) = self.persistence.read().await.as_ref() {
-                User::delete(&u, persistence.get_client().await?.client()).await?;
+                let client = persistence.get_client().await?;
+                AccessKeyPermissions::delete_many(&keys, client.client()).await?;
+                User::delete(&u, client.client()).await?;
             }
         }
         Ok(())
@@ -887,6 +889,37 @@ impl Cache {
         Ok((obj, loc))
     }
 
+    /// All locations of objects that are stored on the given endpoint
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn get_locations_by_endpoint(
+        &self,
+        endpoint_id: &DieselUlid,
+    ) -> Vec<(DieselUlid, ObjectLocation)> {
+        // Collect first to not hold map guards across awaits
+        let resources: Vec<_> = self
+            .resources
+            .iter()
+            .map(|e| (*e.key(), e.value().clone()))
+            .collect();
+
+        let mut locations = Vec::new();
+        for (id, (obj, loc)) in resources {
+            if !obj
+                .read()
+                .await
+                .endpoints
+                .iter()
+                .any(|ep| &ep.id == endpoint_id)
+            {
+                continue;
+            }
+            if let Some(location) = loc.read().await.as_ref() {
+                locations.push((id, location.clone()));
+            }
+        }
+        locations
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn get_path(&self, path: &str) -> Option<DieselUlid> {
         self.paths.get(path).map(|e| *e.value())
@@ -920,6 +953,15 @@ impl Cache {
             .collect()
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn list_by_prefix(&self, prefix: &str) -> Vec<(String, DieselUlid)> {
+        self.paths
+            .range(prefix.to_string()..)
+            .take_while(|e| e.key().starts_with(prefix))
+            .map(|e| (e.key().clone(), *e.value()))
+            .collect()
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     pub fn add_bundle(&self, bundle: Bundle) {
         self.bundles.insert(bundle.id, bundle);
@@ -1254,3 +1296,142 @@ impl Cache {
         Ok(())
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "MC4CAQAwBQYDK2VwBCIEICHl/V9wxvENDJKePwusDhnC7xgaHYV6iHLb0ENJZndj";
+    const PUBLIC_KEY: &str = "MCowBQYDK2VwAyEA2YfYTgb8Y0LTFr+2Rm2Fkdu38eJTfnsMDH2iZHErBH0=";
+
+    pub(crate) async fn init_cache() -> Arc<Cache> {
+        let (sender, _receiver) = async_channel::bounded(1);
+        Cache::new(
+            None::<String>,
+            false,
+            DieselUlid::generate(),
+            PRIVATE_KEY.to_string(),
+            1,
+            sender,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_set_pubkeys() {
+        let cache = init_cache().await;
+
+        cache
+            .add_pubkey(PubKey {
+                id: 1,
+                key: PUBLIC_KEY.to_string(),
+                is_proxy: false,
+            })
+            .await
+            .unwrap();
+        cache
+            .set_pubkeys(vec![
+                PubKey {
+                    id: 2,
+                    key: PUBLIC_KEY.to_string(),
+                    is_proxy: false,
+                },
+                PubKey {
+                    id: 3,
+                    key: PUBLIC_KEY.to_string(),
+                    is_proxy: true,
+                },
+            ])
+            .await
+            .unwrap();
+
+        // Previous keys are replaced
+        assert!(cache.get_pubkey(1).is_err());
+        let (pk, _) = cache.get_pubkey(2).unwrap();
+        assert_eq!(pk.key, PUBLIC_KEY);
+        assert!(!pk.is_proxy);
+        let (pk, _) = cache.get_pubkey(3).unwrap();
+        assert!(pk.is_proxy);
+    }
+
+    #[tokio::test]
+    async fn test_get_locations_by_endpoint() {
+        let cache = init_cache().await;
+        let first_endpoint = DieselUlid::generate();
+        let second_endpoint = DieselUlid::generate();
+        let endpoint = |id| crate::structs::Endpoint {
+            id,
+            variant: crate::structs::SyncVariant::FullSync,
+            status: None,
+        };
+
+        // (endpoints, has location, expected for first endpoint)
+        let mut expected = Vec::new();
+        for (endpoints, with_location, on_first) in [
+            (vec![endpoint(first_endpoint)], true, true),
+            (
+                vec![endpoint(first_endpoint), endpoint(second_endpoint)],
+                true,
+                true,
+            ),
+            (vec![endpoint(second_endpoint)], true, false),
+            (vec![endpoint(first_endpoint)], false, false),
+        ] {
+            let mut object = Object::initialize_now(
+                DieselUlid::generate().to_string(),
+                ObjectType::Project,
+                None,
+            );
+            object.endpoints = endpoints;
+            let id = object.id;
+            cache.upsert_object(object).await.unwrap();
+            if with_location {
+                let location = ObjectLocation {
+                    id: DieselUlid::generate(),
+                    ..Default::default()
+                };
+                cache.update_location(id, location.clone()).await.unwrap();
+                if on_first {
+                    expected.push((id, location));
+                }
+            }
+        }
+
+        let mut locations = cache.get_locations_by_endpoint(&first_endpoint).await;
+        locations.sort_by_key(|(id, _)| *id);
+        expected.sort_by_key(|(id, _)| *id);
+        assert_eq!(locations, expected);
+    }
+
+    #[tokio::test]
+    async fn test_list_by_prefix() {
+        let cache = init_cache().await;
+        let paths = [
+            "bucket/b/object",
+            "bucket/a/object",
+            "bucket/a",
+            "bucket-other/a",
+            "other/bucket/a",
+        ];
+        for path in paths {
+            cache.paths.insert(path.to_string(), DieselUlid::generate());
+        }
+
+        let listed: Vec<String> = cache
+            .list_by_prefix("bucket/")
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(
+            listed,
+            vec!["bucket/a", "bucket/a/object", "bucket/b/object"]
+        );
+
+        let (path, id) = cache.list_by_prefix("bucket/a/").pop().unwrap();
+        assert_eq!(path, "bucket/a/object");
+        assert_eq!(Some(id), cache.get_path("bucket/a/object"));
+        assert!(cache.list_by_prefix("missing/").is_empty());
+    }
+}
@@ -71,6 +71,7 @@ use aruna_rust_api::api::{
 use diesel_ulid::DieselUlid;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::metadata::AsciiMetadataKey;
@@ -83,9 +84,15 @@ use tonic::Streaming;
 use tracing::debug;
 use tracing::error;
 use tracing::trace;
+use tracing::warn;
 use tracing::Instrument;
 
 use super::cache::Cache;
+use crate::CONFIG;
+
+const DEFAULT_CONNECT_ATTEMPTS: u32 = 5;
+const INITIAL_CONNECT_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_CONNECT_BACKOFF: Duration = Duration::from_secs(30);
 
 pub struct GrpcQueryHandler {
     project_service: ProjectServiceClient<Channel>,
@@ -129,10 +136,11 @@ impl GrpcQueryHandler {
                 e
             })?
         };
-        let channel = endpoint.connect().await.map_err(|e| {
-            error!(error = ?e, msg = e.to_string());
-            e
-        })?;
+        let attempts = CONFIG
+            .proxy
+            .max_connect_attempts
+            .unwrap_or(DEFAULT_CONNECT_ATTEMPTS);
+        let channel = connect_with_backoff(&endpoint, attempts, INITIAL_CONNECT_BACKOFF).await?;
 
         let project_service = ProjectServiceClient::new(channel.clone());
 
@@ -190,6 +198,32 @@ impl GrpcQueryHandler {
     }
 }
 
+/// Connects to the ArunaServer, doubling the backoff after each failed attempt
+async fn connect_with_backoff(
+    endpoint: &tonic::transport::Endpoint,
+    attempts: u32,
+    initial_backoff: Duration,
+) -> Result<Channel> {
+    let mut backoff = initial_backoff;
+    for attempt in 1..=attempts.max(1) {
+        match endpoint.connect().await {
+            Ok(channel) => return Ok(channel),
+            Err(e) if attempt < attempts => {
+                warn!(error = ?e, attempt, "Unable to connect to ArunaServer, retrying");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_CONNECT_BACKOFF);
+            }
+            Err(e) => {
+                error!(error = ?e, msg = e.to_string());
+            }
+        }
+    }
+    Err(anyhow!(
+        "Unable to connect to ArunaServer after {} attempts",
+        attempts.max(1)
+    ))
+}
+
 // Aruna grpc request section
 impl GrpcQueryHandler {
     pub fn add_token_to_md(md: &mut MetadataMap, token: &str) -> Result<()> {
@@ -1427,3 +1461,24 @@ pub fn sort_objects(res: &mut [DPObject]) {
         (ObjectType::Object, ObjectType::Object) => std::cmp::Ordering::Equal,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connect_with_backoff_exhausts_attempts() {
+        // Nothing listens on the discard port
+        let endpoint = Channel::from_static("http://127.0.0.1:9");
+        let start = std::time::Instant::now();
+        let err = connect_with_backoff(&endpoint, 3, Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unable to connect to ArunaServer after 3 attempts"
+        );
+        // Two backoffs: 10ms + 20ms
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+}
@@ -33,6 +33,10 @@ impl Config {
     pub fn get_rules(&self) -> Vec<Rule> {
         self.rules.clone().unwrap_or_default()
     }
+
+    pub fn get_max_object_size(&self) -> Option<u64> {
+        self.frontend.as_ref().and_then(|f| f.max_object_size)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -47,6 +51,7 @@ pub struct Proxy {
     pub aruna_url: Option<String>,
     pub grpc_server: String,
     pub replication_interval: Option<u64>,
+    pub max_connect_attempts: Option<u32>,
 }
 
 impl Proxy {
@@ -119,6 +124,8 @@ pub enum Persistence {
         password: Option<String>,
         database: String,
         schema: String,
+        connect_timeout: Option<u64>,
+        statement_timeout: Option<u64>,
     },
 }
 
@@ -131,6 +138,7 @@ impl Persistence {
             password,
             database,
             schema,
+            ..
         } = self;
 
         if host.is_empty() {
@@ -169,6 +177,8 @@ pub struct Frontend {
     pub server: String,
     pub hostname: String,
     pub cors_exception: Option<String>,
+    pub shutdown_timeout: Option<u64>,
+    pub max_object_size: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
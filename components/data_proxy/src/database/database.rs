@@ -1,5 +1,9 @@
-use anyhow::Result;
-use deadpool_postgres::{Client, Config, ManagerConfig, Pool, RecyclingMethod, Runtime};
+use anyhow::{anyhow, bail, Result};
+use deadpool_postgres::{
+    Client, Config, ManagerConfig, Pool, PoolConfig, RecyclingMethod, Runtime, Timeouts,
+};
+use std::collections::BTreeMap;
+use std::time::Duration;
 use tokio_postgres::NoTls;
 
 use crate::{config::Persistence, CONFIG};
@@ -18,6 +22,8 @@ impl Database {
             password,
             database,
             schema,
+            connect_timeout,
+            statement_timeout,
         } = CONFIG
             .persistence
             .as_ref()
@@ -36,13 +42,27 @@ impl Database {
         cfg.manager = Some(ManagerConfig {
             recycling_method: RecyclingMethod::Fast,
         });
+        if let Some(connect_timeout) = connect_timeout {
+            let timeout = Duration::from_secs(*connect_timeout);
+            cfg.connect_timeout = Some(timeout);
+            cfg.pool = Some(PoolConfig {
+                timeouts: Timeouts {
+                    wait: Some(timeout),
+                    create: Some(timeout),
+                    recycle: Some(timeout),
+                },
+                ..Default::default()
+            });
+        }
+        // Cancels statements server-side, e.g. when stuck on locks
+        cfg.options = statement_timeout.map(|ms| format!("-c statement_timeout={ms}"));
         let pool = cfg.create_pool(Some(Runtime::Tokio1), NoTls).map_err(|e| {
             tracing::error!(error = ?e, msg = e.to_string());
             e
         })?;
 
         Database::initialize_db(
-            &pool.get().await.map_err(|e| {
+            &mut pool.get().await.map_err(|e| {
                 tracing::error!(error = ?e, msg = e.to_string());
                 e
             })?,
@@ -55,16 +75,58 @@ impl Database {
         })
     }
 
+    /// Applies all migrations of the directory not yet recorded in
+    /// `schema_migrations`, each in its own transaction. A single schema file
+    /// is unversioned and re-applied on every start.
     #[tracing::instrument(level = "trace", skip(client))]
-    pub async fn initialize_db(client: &Client, schema: String) -> Result<()> {
-        let initial = tokio::fs::read_to_string(schema).await.map_err(|e| {
-            tracing::error!(error = ?e, msg = e.to_string());
-            e
-        })?;
-        client.batch_execute(&initial).await.map_err(|e| {
-            tracing::error!(error = ?e, msg = e.to_string());
-            e
-        })?;
+    pub async fn initialize_db(client: &mut Client, schema: String) -> Result<()> {
+        if !tokio::fs::metadata(&schema).await?.is_dir() {
+            let schema = tokio::fs::read_to_string(&schema).await?;
+            client.batch_execute(&schema).await.map_err(|e| {
+                tracing::error!(error = ?e, msg = e.to_string());
+                e
+            })?;
+            return Ok(());
+        }
+
+        client
+            .batch_execute(
+                "CREATE TABLE IF NOT EXISTS schema_migrations (
+                    version INT PRIMARY KEY,
+                    applied_at TIMESTAMP NOT NULL DEFAULT NOW()
+                );",
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!(error = ?e, msg = e.to_string());
+                e
+            })?;
+
+        for (version, migration) in load_migrations(&schema).await? {
+            let transaction = client.transaction().await?;
+            let applied = transaction
+                .query_opt(
+                    "SELECT version FROM schema_migrations WHERE version = $1;",
+                    &[&version],
+                )
+                .await?
+                .is_some();
+            if applied {
+                continue;
+            }
+            transaction.batch_execute(&migration).await.map_err(|e| {
+                tracing::error!(error = ?e, msg = e.to_string(), version);
+                e
+            })?;
+            transaction
+                .execute(
+                    "INSERT INTO schema_migrations (version) VALUES ($1);",
+                    &[&version],
+                )
+                .await?;
+            transaction.commit().await?;
+            tracing::info!(version, "applied schema migration");
+        }
         Ok(())
     }
 
@@ -75,4 +137,189 @@ impl Database {
             e
         })?)
     }
+
+    /// Checks that a connection can be acquired and answers within the timeout
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub async fn health_check(&self, timeout: Duration) -> Result<()> {
+        tokio::time::timeout(timeout, async {
+            let client = self.get_client().await?;
+            client.simple_query("SELECT 1").await?;
+            Ok::<(), anyhow::Error>(())
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!(error = ?e, msg = e.to_string());
+            anyhow::anyhow!("Database health check timed out")
+        })?
+    }
+}
+
+/// Returns the numbered migrations named `<version>_<name>.sql` of the
+/// directory in ascending order
+async fn load_migrations(schema: &str) -> Result<Vec<(i32, String)>> {
+    let mut migrations = BTreeMap::new();
+    let mut entries = tokio::fs::read_dir(schema).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(stem) = file_name.strip_suffix(".sql") else {
+            continue;
+        };
+        let version = stem
+            .split_once('_')
+            .map_or(stem, |(version, _)| version)
+            .parse::<i32>()
+            .map_err(|_| anyhow!("Invalid migration file name: {file_name}"))?;
+        let migration = tokio::fs::read_to_string(entry.path()).await?;
+        if migrations.insert(version, migration).is_some() {
+            bail!("Duplicate migration version: {version}");
+        }
+    }
+    Ok(migrations.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_migrations() {
+        let dir = std::env::temp_dir().join(format!(
+            "migrations_{}",
+            diesel_ulid::DieselUlid::generate()
+        ));
+        tokio::fs::create_dir(&dir).await.unwrap();
+        for (name, content) in [
+            ("002_add_index.sql", "second"),
+            ("001_initial.sql", "first"),
+            ("10_later.sql", "tenth"),
+            ("README.md", "ignored"),
+        ] {
+            tokio::fs::write(dir.join(name), content).await.unwrap();
+        }
+
+        let migrations = load_migrations(dir.to_str().unwrap()).await.unwrap();
+        assert_eq!(
+            migrations,
+            vec![
+                (1, "first".to_string()),
+                (2, "second".to_string()),
+                (10, "tenth".to_string())
+            ]
+        );
+
+        // Versions must be unique
+        tokio::fs::write(dir.join("001_duplicate.sql"), "duplicate")
+            .await
+            .unwrap();
+        assert!(load_migrations(dir.to_str().unwrap()).await.is_err());
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_initialize_db_twice() {
+        // Requires the test database from tests/setup.sh, runs in its own schema
+        let database = Database::new().await.unwrap();
+        let mut client = database.get_client().await.unwrap();
+        let search_path = format!(
+            "migrations_{}",
+            diesel_ulid::DieselUlid::generate()
+                .to_string()
+                .to_lowercase()
+        );
+        client
+            .batch_execute(&format!(
+                "CREATE SCHEMA {search_path}; SET search_path TO {search_path};"
+            ))
+            .await
+            .unwrap();
+
+        // Migrations that fail or duplicate rows when applied twice
+        let dir = std::env::temp_dir().join(&search_path);
+        tokio::fs::create_dir(&dir).await.unwrap();
+        for (name, content) in [
+            ("001_create.sql", "CREATE TABLE counter (id INT);"),
+            ("002_insert.sql", "INSERT INTO counter VALUES (2);"),
+        ] {
+            tokio::fs::write(dir.join(name), content).await.unwrap();
+        }
+        let migrations = dir.to_str().unwrap().to_string();
+        Database::initialize_db(&mut client, migrations.clone())
+            .await
+            .unwrap();
+        Database::initialize_db(&mut client, migrations.clone())
+            .await
+            .unwrap();
+
+        // New migrations are applied on the next start
+        tokio::fs::write(
+            dir.join("003_insert.sql"),
+            "INSERT INTO counter VALUES (3);",
+        )
+        .await
+        .unwrap();
+        Database::initialize_db(&mut client, migrations)
+            .await
+            .unwrap();
+
+        let rows: Vec<i32> = client
+            .query("SELECT id FROM counter ORDER BY id;", &[])
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+        assert_eq!(rows, vec![2, 3]);
+        let versions: Vec<i32> = client
+            .query(
+                "SELECT version FROM schema_migrations ORDER BY version;",
+                &[],
+            )
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+        assert_eq!(versions, vec![1, 2, 3]);
+
+        client
+            .batch_execute(&format!("DROP SCHEMA {search_path} CASCADE;"))
+            .await
+            .unwrap();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        // Requires the test database from tests/setup.sh
+        let database = Database::new().await.unwrap();
+        database.health_check(Duration::from_secs(5)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_check_timeout() {
+        // Accepts connections but never answers the startup handshake
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+
+        let mut cfg = Config::new();
+        cfg.host = Some("127.0.0.1".to_string());
+        cfg.port = Some(port);
+        cfg.user = Some("test".to_string());
+        cfg.dbname = Some("test".to_string());
+        let database = Database {
+            connection_pool: cfg.create_pool(Some(Runtime::Tokio1), NoTls).unwrap(),
+        };
+
+        let err = database
+            .health_check(Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Database health check timed out");
+    }
 }
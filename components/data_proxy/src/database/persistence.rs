@@ -56,6 +56,14 @@ pub trait WithGenericBytes<
 {
     fn get_table() -> Table;
     async fn upsert(&self, client: &Client) -> Result<()> {
+        self.upsert_returning(client).await?;
+        Ok(())
+    }
+
+    async fn upsert_returning(&self, client: &Client) -> Result<Self>
+    where
+        Self: WithGenericBytes<X, T>,
+    {
         let generic: GenericBytes<X, T> = match self.clone().try_into() {
             Ok(generic) => generic,
             Err(e) => {
@@ -65,22 +73,31 @@ pub trait WithGenericBytes<
         };
 
         let query = format!(
-            "INSERT INTO {} (id, data) VALUES ($1, $2::JSONB) ON CONFLICT (id) DO UPDATE SET data = $2;",
+            "INSERT INTO {} (id, data) VALUES ($1, $2::JSONB) ON CONFLICT (id) DO UPDATE SET data = $2 RETURNING *;",
             Self::get_table()
         );
         let prepared = client.prepare(&query).await.map_err(|e| {
             error!(error = ?e, msg = e.to_string());
             e
         })?;
-
-        client
-            .query(&prepared, &[&generic.id, &generic.data])
+        let row = client
+            .query_one(&prepared, &[&generic.id, &generic.data])
             .await
             .map_err(|e| {
                 error!(error = ?e, msg = e.to_string());
                 e
             })?;
-        Ok(())
+        match Self::try_from(GenericBytes {
+            id: row.get::<&str, X>("id"),
+            data: row.get("data"),
+            table: Self::get_table(),
+        }) {
+            Ok(generic) => Ok(generic),
+            Err(e) => {
+                error!(error = ?e, msg = e.to_string());
+                Err(anyhow!("Failed to convert to GenericBytes, {:?}", e))
+            }
+        }
     }
 
     async fn get_all(client: &Client) -> Result<Vec<Self>>
@@ -185,6 +202,19 @@ pub trait WithGenericBytes<
         Ok(())
     }
 
+    async fn delete_many(ids: &[X], client: &Client) -> Result<()> {
+        let query = format!("DELETE FROM {} WHERE id = ANY($1);", Self::get_table());
+        let prepared = client.prepare(&query).await.map_err(|e| {
+            error!(error = ?e, msg = e.to_string());
+            e
+        })?;
+        client.execute(&prepared, &[&ids]).await.map_err(|e| {
+            error!(error = ?e, msg = e.to_string());
+            e
+        })?;
+        Ok(())
+    }
+
     async fn delete_all(client: &Client) -> Result<()> {
         let query = format!("DELETE FROM {};", Self::get_table());
         let prepared = client.prepare(&query).await.map_err(|e| {
@@ -327,3 +357,94 @@ impl LocationBinding {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Persistence;
+    use crate::database::database::Database;
+    use crate::structs::PubKey;
+    use crate::CONFIG;
+
+    /// Requires the test database from tests/setup.sh, returns a client with
+    /// all tables in a new schema of its own
+    async fn schema_client(database: &Database) -> (deadpool_postgres::Client, String) {
+        let mut client = database.get_client().await.unwrap();
+        let search_path = format!(
+            "persistence_{}",
+            DieselUlid::generate().to_string().to_lowercase()
+        );
+        client
+            .batch_execute(&format!(
+                "CREATE SCHEMA {search_path}; SET search_path TO {search_path};"
+            ))
+            .await
+            .unwrap();
+        let Persistence::Postgres { schema, .. } = CONFIG.persistence.as_ref().unwrap();
+        Database::initialize_db(&mut client, schema.to_string())
+            .await
+            .unwrap();
+        (client, search_path)
+    }
+
+    fn pub_key(id: i16) -> PubKey {
+        PubKey {
+            id,
+            key: format!("key_{id}"),
+            is_proxy: id % 2 == 0,
+        }
+    }
+
+    #[test]
+    fn test_table_names() {
+        let tables = [
+            (Table::Objects, "objects"),
+            (Table::Users, "users"),
+            (Table::PubKeys, "pub_keys"),
+            (Table::ObjectLocations, "object_locations"),
+            (Table::Permissions, "permissions"),
+            (Table::Multiparts, "multiparts"),
+        ];
+        for (table, name) in tables {
+            assert_eq!(table.to_string(), name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_many() {
+        let database = Database::new().await.unwrap();
+        let (client, search_path) = schema_client(&database).await;
+        for id in 1..=4 {
+            pub_key(id).upsert(&client).await.unwrap();
+        }
+
+        PubKey::delete_many(&[1, 3], &client).await.unwrap();
+        let mut remaining = PubKey::get_all(&client).await.unwrap();
+        remaining.sort();
+        assert_eq!(remaining, vec![pub_key(2), pub_key(4)]);
+
+        client
+            .batch_execute(&format!("DROP SCHEMA {search_path} CASCADE;"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upsert_returning() {
+        let database = Database::new().await.unwrap();
+        let (client, search_path) = schema_client(&database).await;
+
+        let mut key = pub_key(1);
+        assert_eq!(key.upsert_returning(&client).await.unwrap(), key);
+
+        // Conflicts update the stored data
+        key.key = "rotated".to_string();
+        assert_eq!(key.upsert_returning(&client).await.unwrap(), key);
+        assert_eq!(PubKey::get(&1, &client).await.unwrap(), key);
+
+        client
+            .batch_execute(&format!("DROP SCHEMA {search_path} CASCADE;"))
+            .await
+            .unwrap();
+    }
+}
@@ -1,9 +1,15 @@
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use anyhow::Result;
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
 use http::Method;
+use http::StatusCode;
 use rand::distributions::DistString;
 use reqsign::{AwsCredential, AwsV4Signer};
+use s3s::{s3_error, S3Error};
 use url::Url;
 
 #[tracing::instrument(
@@ -151,6 +157,63 @@ pub fn random_string(len: usize) -> String {
         .to_ascii_lowercase()
 }
 
+/// Rejects objects larger than `max_size` with `413 Payload Too Large`
+pub fn check_object_size(size: u64, max_size: Option<u64>) -> Result<(), S3Error> {
+    match max_size {
+        Some(max) if size > max => {
+            let mut err = s3_error!(
+                EntityTooLarge,
+                "Object size of {} bytes exceeds the maximum of {} bytes",
+                size,
+                max
+            );
+            err.set_status_code(StatusCode::PAYLOAD_TOO_LARGE);
+            Err(err)
+        }
+        _ => Ok(()),
+    }
+}
+
+type StreamResult = Result<Bytes, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Passes at most `max_size` bytes and ends the stream once it exceeds them,
+/// `received` counts the bytes read from the stream
+pub fn limit_stream_size<S>(
+    stream: S,
+    max_size: Option<u64>,
+    received: Arc<AtomicU64>,
+) -> impl Stream<Item = StreamResult> + Unpin + Send + Sync
+where
+    S: Stream<Item = StreamResult> + Unpin + Send + Sync,
+{
+    stream.scan(false, move |exceeded, chunk| {
+        if *exceeded {
+            return futures_util::future::ready(None);
+        }
+        let chunk = chunk.map(|chunk| {
+            let len = chunk.len() as u64;
+            let before = received.fetch_add(len, Ordering::Relaxed);
+            match max_size {
+                Some(max) if before + len > max => {
+                    *exceeded = true;
+                    chunk.slice(..max.saturating_sub(before) as usize)
+                }
+                _ => chunk,
+            }
+        });
+        futures_util::future::ready(Some(chunk))
+    })
+}
+
+/// Checks a base64 encoded Content-MD5 header against a hex encoded md5 digest
+pub fn content_md5_matches(content_md5: &str, md5_hex: &str) -> bool {
+    use base64::{engine::general_purpose, Engine};
+    general_purpose::STANDARD
+        .decode(content_md5.trim())
+        .map(|digest| hex::encode(digest) == md5_hex.to_ascii_lowercase())
+        .unwrap_or(false)
+}
+
 pub fn bucket_path_from_pathstring(path: &str) -> Result<(String, String)> {
     if path.starts_with("s3://") {
         let parts = path[5..].split_once('/');
@@ -186,3 +249,25 @@ impl<T, S> IntoOption for HashSet<T, S> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_md5_matches() {
+        // md5("hello world")
+        let md5_hex = "5eb63bbbe01eeed093cb22bb8f5acdc3";
+        assert!(content_md5_matches("XrY7u+Ae7tCTyyK7j1rNww==", md5_hex));
+        assert!(!content_md5_matches("1B2M2Y8AsgTpgAmY7PhCfg==", md5_hex));
+        assert!(!content_md5_matches("not base64", md5_hex));
+    }
+
+    #[test]
+    fn test_check_object_size() {
+        assert!(check_object_size(1024, None).is_ok());
+        assert!(check_object_size(1024, Some(1024)).is_ok());
+        let err = check_object_size(1025, Some(1024)).unwrap_err();
+        assert_eq!(err.status_code(), Some(StatusCode::PAYLOAD_TOO_LARGE));
+    }
+}
@@ -15,9 +15,11 @@ use lazy_static::lazy_static;
 use regex::Regex;
 use std::panic;
 use std::{net::SocketAddr, sync::Arc};
+use tokio::sync::watch;
 use tokio::try_join;
 use tonic::transport::Server;
 use tracing::error;
+use tracing::info;
 use tracing::info_span;
 use tracing::trace;
 use tracing::Instrument;
@@ -139,6 +141,14 @@ async fn main() -> Result<()> {
         };
     });
 
+    // Notify servers about termination to shut down gracefully
+    let (shutdown_sender, shutdown_receiver) = watch::channel(());
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        info!("received shutdown signal");
+        shutdown_sender.send(()).ok();
+    });
+
     trace!("init s3 server");
     let cache_clone = cache.clone();
     let s3_server = if let Some(frontend) = &CONFIG.frontend {
@@ -158,6 +168,7 @@ async fn main() -> Result<()> {
 
     let proxy_grpc_addr = CONFIG.proxy.grpc_server.parse::<SocketAddr>()?;
 
+    let mut grpc_shutdown = shutdown_receiver.clone();
     let grpc_server_handle = tokio::spawn(
         async move {
             let mut builder = Server::builder()
@@ -187,7 +198,11 @@ async fn main() -> Result<()> {
                 )));
             };
 
-            builder.serve(proxy_grpc_addr).await
+            builder
+                .serve_with_shutdown(proxy_grpc_addr, async move {
+                    grpc_shutdown.changed().await.ok();
+                })
+                .await
         }
         .instrument(info_span!("grpc_server_run")),
     )
@@ -197,7 +212,17 @@ async fn main() -> Result<()> {
     });
 
     if let Some(s3_server) = s3_server {
-        match try_join!(s3_server.run(), grpc_server_handle) {
+        let shutdown_timeout = Duration::from_secs(
+            CONFIG
+                .frontend
+                .as_ref()
+                .and_then(|frontend| frontend.shutdown_timeout)
+                .unwrap_or(30),
+        );
+        match try_join!(
+            s3_server.run(shutdown_receiver, shutdown_timeout),
+            grpc_server_handle
+        ) {
             Ok(_) => Ok(()),
             Err(err) => {
                 error!("{}", err);
@@ -209,3 +234,33 @@ async fn main() -> Result<()> {
         Ok(())
     }
 }
+
+/// Resolves on Ctrl+C or, on unix systems, on SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            error!(error = ?err, "unable to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                error!(error = ?err, "unable to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
@@ -1,10 +1,13 @@
 use crate::caching::cache::Cache;
+use crate::structs::CheckAccessResult;
+use http::Method;
 use s3s::{
     auth::{S3Auth, S3AuthContext, SecretKey},
+    path::S3Path,
     s3_error, S3Result,
 };
 use std::sync::Arc;
-use tracing::debug;
+use tracing::{debug, info, warn};
 
 /// Aruna authprovider
 pub struct AuthProvider {
@@ -39,12 +42,103 @@ impl S3Auth for AuthProvider {
             Some(auth) => {
                 let result = auth
                     .check_access(cx.credentials(), cx.method(), cx.s3_path(), cx.headers())
-                    .await?;
+                    .await;
+                log_access(
+                    cx.credentials().map(|creds| creds.access_key.as_str()),
+                    cx.method(),
+                    cx.s3_path(),
+                    &result,
+                );
 
-                cx.extensions_mut().insert(result);
+                cx.extensions_mut().insert(result?);
                 Ok(())
             }
             None => Ok(()),
         }
     }
 }
+
+/// Audit log entry for each access decision, only the access key is recorded,
+/// never the secret
+fn log_access(
+    access_key: Option<&str>,
+    method: &Method,
+    path: &S3Path,
+    result: &S3Result<CheckAccessResult>,
+) {
+    let access_key = access_key.unwrap_or("anonymous");
+    let path = match path {
+        S3Path::Root => "/".to_string(),
+        S3Path::Bucket { bucket } => format!("/{bucket}"),
+        S3Path::Object { bucket, key } => format!("/{bucket}/{key}"),
+    };
+    match result {
+        Ok(_) => info!(access_key, %method, path, outcome = "allow", "s3 access"),
+        Err(err) => warn!(
+            access_key,
+            %method,
+            path,
+            outcome = "deny",
+            code = ?err.code(),
+            "s3 access"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    // Records the fields of every event as "name=value"
+    #[derive(Clone, Default)]
+    struct CaptureLayer {
+        events: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    struct FieldVisitor(Vec<String>);
+
+    impl Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            let mut visitor = FieldVisitor(Vec::new());
+            event.record(&mut visitor);
+            self.events.lock().unwrap().push(visitor.0);
+        }
+    }
+
+    #[test]
+    fn test_log_denied_access() {
+        let layer = CaptureLayer::default();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            log_access(
+                Some("access_key_id"),
+                &Method::GET,
+                &S3Path::object("bucket", "key"),
+                &Err(s3_error!(AccessDenied, "Forbidden")),
+            );
+        });
+
+        let events = layer.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert!(event.contains(&"outcome=deny".to_string()));
+        assert!(event.contains(&"access_key=access_key_id".to_string()));
+        assert!(event.contains(&"method=GET".to_string()));
+        assert!(event.contains(&"path=/bucket/key".to_string()));
+        assert!(event.contains(&"code=AccessDenied".to_string()));
+    }
+}
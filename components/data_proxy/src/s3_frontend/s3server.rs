@@ -13,6 +13,7 @@ use hyper::service::Service;
 use hyper_util::rt::TokioExecutor;
 use hyper_util::rt::TokioIo;
 use hyper_util::server::conn::auto::Builder as ConnBuilder;
+use hyper_util::server::graceful::GracefulShutdown;
 use s3s::s3_error;
 use s3s::service::S3Service;
 use s3s::service::S3ServiceBuilder;
@@ -23,9 +24,12 @@ use std::convert::Infallible;
 use std::future::ready;
 use std::future::Ready;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::sync::watch;
 use tracing::error;
 use tracing::info;
+use tracing::warn;
 
 pub struct S3Server {
     s3service: S3Service,
@@ -62,8 +66,14 @@ impl S3Server {
             address: address.into(),
         })
     }
-    #[tracing::instrument(level = "trace", skip(self))]
-    pub async fn run(self) -> Result<()> {
+    /// Serves the S3 frontend until the shutdown signal is received. Open
+    /// connections are then given `shutdown_timeout` to finish their requests.
+    #[tracing::instrument(level = "trace", skip(self, shutdown))]
+    pub async fn run(
+        self,
+        mut shutdown: watch::Receiver<()>,
+        shutdown_timeout: Duration,
+    ) -> Result<()> {
         // Run server
         let listener = TcpListener::bind(&self.address).await.map_err(|e| {
             error!(error = ?e, msg = e.to_string());
@@ -75,26 +85,41 @@ impl S3Server {
         let service = WrappingService(self.s3service.into_shared());
 
         let connection = ConnBuilder::new(TokioExecutor::new());
+        let graceful = GracefulShutdown::new();
 
-        let server = async move {
-            loop {
-                let (socket, _) = match listener.accept().await {
-                    Ok(ok) => ok,
-                    Err(err) => {
-                        tracing::error!("error accepting connection: {err}");
-                        continue;
-                    }
-                };
-                let service = service.clone();
-                let conn = connection.clone();
-                tokio::spawn(async move {
-                    let _ = conn.serve_connection(TokioIo::new(socket), service).await;
-                });
+        info!("server is running at http://{local_addr}");
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (socket, _) = match accepted {
+                        Ok(ok) => ok,
+                        Err(err) => {
+                            tracing::error!("error accepting connection: {err}");
+                            continue;
+                        }
+                    };
+                    let conn = connection
+                        .serve_connection(TokioIo::new(socket), service.clone())
+                        .into_owned();
+                    let conn = graceful.watch(conn);
+                    tokio::spawn(async move {
+                        let _ = conn.await;
+                    });
+                }
+                // Sender dropped or shutdown signaled
+                _ = shutdown.changed() => break,
             }
-        };
+        }
 
-        let _task = tokio::spawn(server);
-        info!("server is running at http://{local_addr}");
+        // Stop accepting new connections and let open ones finish
+        drop(listener);
+        info!("shutting down server at http://{local_addr}");
+        tokio::select! {
+            _ = graceful.shutdown() => info!("all connections closed"),
+            _ = tokio::time::sleep(shutdown_timeout) => {
+                warn!("timed out waiting for open connections to close")
+            }
+        }
 
         Ok(())
     }
@@ -208,3 +233,30 @@ impl<T, S: Clone> Service<T> for MakeService<S> {
         ready(Ok(self.0.clone()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoopS3;
+
+    #[async_trait::async_trait]
+    impl s3s::S3 for NoopS3 {}
+
+    #[tokio::test]
+    async fn test_shutdown_while_idle() {
+        let server = S3Server {
+            s3service: S3ServiceBuilder::new(NoopS3).build(),
+            address: "127.0.0.1:0".to_string(),
+        };
+        let (sender, receiver) = watch::channel(());
+        let handle = tokio::spawn(server.run(receiver, Duration::from_secs(5)));
+
+        sender.send(()).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("server did not shut down in time")
+            .unwrap();
+        assert!(result.is_ok());
+    }
+}
@@ -4,6 +4,7 @@ use super::utils::ranges::calculate_ranges;
 use crate::bundler::bundle_helper::get_bundle;
 use crate::caching::cache::Cache;
 use crate::data_backends::storage_backend::StorageBackend;
+use crate::helpers::{check_object_size, content_md5_matches, limit_stream_size};
 use crate::s3_frontend::utils::list_objects::list_response;
 use crate::structs::CheckAccessResult;
 use crate::structs::NewOrExistingObject;
@@ -52,6 +53,7 @@ use sha2::Sha256;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::pin;
 use tower::buffer;
@@ -65,6 +67,7 @@ use tracing::Instrument;
 pub struct ArunaS3Service {
     backend: Arc<Box<dyn StorageBackend>>,
     cache: Arc<Cache>,
+    max_object_size: Option<u64>,
 }
 
 impl Debug for ArunaS3Service {
@@ -80,6 +83,7 @@ impl ArunaS3Service {
         Ok(ArunaS3Service {
             backend: backend.clone(),
             cache,
+            max_object_size: CONFIG.get_max_object_size(),
         })
     }
 }
@@ -1165,7 +1169,7 @@ impl S3 for ArunaS3Service {
                     "Missing or invalid (0) content-length"
                 ));
             }
-            _ => {}
+            Some(len) => check_object_size(len as u64, self.max_object_size)?,
         };
 
         let CheckAccessResult {
@@ -1188,39 +1192,17 @@ impl S3 for ArunaS3Service {
 
         let (_, collection, dataset, object, location_state) = states.to_new_or_existing()?;
 
-        let (mut new_object, was_init) = match object {
+        // Revisions of existing objects are only created once the upload is verified
+        let (mut new_object, was_init, needs_revision) = match object {
             NewOrExistingObject::Existing(ob) => {
                 if ob.object_status == Status::Initializing {
                     trace!("Object is initializing");
-                    (ob, true)
+                    (ob, true, false)
                 } else {
-                    let mut new_revision = if let Some(handler) =
-                        self.cache.aruna_client.read().await.as_ref()
-                    {
-                        if let Some(token) = &impersonating_token {
-                            handler
-                                .init_object_update(ob, token, true)
-                                .await
-                                .map_err(|_| {
-                                    error!(error = "Object update failed");
-                                    s3_error!(InternalError, "Object update failed")
-                                })?
-                        } else {
-                            error!("missing impersonating token");
-                            return Err(s3_error!(InternalError, "Token creation failed"));
-                        }
-                    } else {
-                        error!("ArunaServer client not available");
-                        return Err(s3_error!(InternalError, "ArunaServer client not available"));
-                    };
-                    new_revision.hashes = HashMap::default();
-                    new_revision.synced = false;
-                    new_revision.children = None;
-                    new_revision.dynamic = false;
-                    (new_revision, true)
+                    (ob, true, true)
                 }
             }
-            NewOrExistingObject::Missing(object) => (object, false),
+            NewOrExistingObject::Missing(object) => (object, false, false),
             NewOrExistingObject::None => {
                 return Err(s3_error!(
                     InvalidObjectState,
@@ -1253,12 +1235,14 @@ impl S3 for ArunaS3Service {
             HashingTransformer::new_with_backchannel(Sha256::new(), "sha256".to_string());
         let (final_size_trans, final_size_recv) = SizeProbe::new();
 
+        // Stops reading the body as soon as it exceeds the max. object size
+        let received = Arc::new(AtomicU64::new(0));
         match req.input.body {
             Some(data) => {
                 let (tx, rx) = async_channel::bounded(10);
 
                 let mut awr = GenericStreamReadWriter::new_with_sink(
-                    data,
+                    limit_stream_size(data, self.max_object_size, received.clone()),
                     BufferedS3Sink::new(
                         self.backend.clone(),
                         location.clone(),
@@ -1318,10 +1302,22 @@ impl S3 for ArunaS3Service {
                 awr = awr.add_transformer(final_sha_trans);
                 awr = awr.add_transformer(final_size_trans);
 
-                awr.process().await.map_err(|_| {
+                let processed = awr.process().await;
+                let received = received.load(Ordering::Relaxed);
+                if let Err(err) = check_object_size(received, self.max_object_size) {
+                    error!(received, "Upload exceeds max. object size");
+                    if let Err(err) = self.backend.delete_object(location.clone()).await {
+                        error!(error = ?err, "Unable to delete oversized upload");
+                    }
+                    return Err(err);
+                }
+                if processed.is_err() {
                     error!(error = "Internal data transformer processing error");
-                    s3_error!(InternalError, "Internal data transformer processing error")
-                })?;
+                    return Err(s3_error!(
+                        InternalError,
+                        "Internal data transformer processing error"
+                    ));
+                }
             }
             None => {
                 error!("Empty body is not allowed");
@@ -1329,6 +1325,63 @@ impl S3 for ArunaS3Service {
             }
         }
 
+        let md5_initial = Some(initial_md5_recv.try_recv().map_err(|_| {
+            error!(error = "Unable to md5 hash initial data");
+            s3_error!(InternalError, "Unable to md5 hash initial data")
+        })?);
+
+        let initial_size: u64 = initial_size_recv.try_recv().map_err(|_| {
+            error!(error = "Unable to get size");
+            s3_error!(InternalError, "Unable to get size")
+        })?;
+
+        // Verify client provided checksum before anything else gets created
+        if let (Some(content_md5), Some(md5)) = (&req.input.content_md5, &md5_initial) {
+            if !content_md5_matches(content_md5, md5) {
+                error!(?content_md5, ?md5, "Content-MD5 mismatch");
+                if let Err(err) = self.backend.delete_object(location.clone()).await {
+                    error!(error = ?err, "Unable to delete corrupted upload");
+                }
+                return Err(s3_error!(
+                    BadDigest,
+                    "The Content-MD5 you specified did not match what we received"
+                ));
+            }
+        }
+
+        if needs_revision {
+            let revision = if let Some(handler) = self.cache.aruna_client.read().await.as_ref() {
+                if let Some(token) = &impersonating_token {
+                    handler
+                        .init_object_update(new_object, token, true)
+                        .await
+                        .map_err(|_| {
+                            error!(error = "Object update failed");
+                            s3_error!(InternalError, "Object update failed")
+                        })
+                } else {
+                    error!("missing impersonating token");
+                    Err(s3_error!(InternalError, "Token creation failed"))
+                }
+            } else {
+                error!("ArunaServer client not available");
+                Err(s3_error!(InternalError, "ArunaServer client not available"))
+            };
+            new_object = match revision {
+                Ok(revision) => revision,
+                Err(err) => {
+                    if let Err(err) = self.backend.delete_object(location.clone()).await {
+                        error!(error = ?err, "Unable to delete unreferenced upload");
+                    }
+                    return Err(err);
+                }
+            };
+            new_object.hashes = HashMap::default();
+            new_object.synced = false;
+            new_object.children = None;
+            new_object.dynamic = false;
+        }
+
         let mut collection_id = None;
         if let NewOrExistingObject::Missing(collection) = collection {
             if let Some(handler) = self.cache.aruna_client.read().await.as_ref() {
@@ -1373,10 +1426,6 @@ impl S3 for ArunaS3Service {
 
         // Fetch calculated hashes
         trace!("fetching hashes");
-        let md5_initial = Some(initial_md5_recv.try_recv().map_err(|_| {
-            error!(error = "Unable to md5 hash initial data");
-            s3_error!(InternalError, "Unable to md5 hash initial data")
-        })?);
         let sha_initial = Some(initial_sha_recv.try_recv().map_err(|_| {
             error!(error = "Unable to sha hash initial data");
             s3_error!(InternalError, "Unable to sha hash initial data")
@@ -1385,10 +1434,6 @@ impl S3 for ArunaS3Service {
             error!(error = "Unable to sha hash final data");
             s3_error!(InternalError, "Unable to sha hash final data")
         })?;
-        let initial_size: u64 = initial_size_recv.try_recv().map_err(|_| {
-            error!(error = "Unable to get size");
-            s3_error!(InternalError, "Unable to get size")
-        })?;
         let final_size: u64 = final_size_recv.try_recv().map_err(|_| {
             error!(error = "Unable to get size");
             s3_error!(InternalError, "Unable to get size")
@@ -1512,6 +1557,7 @@ impl S3 for ArunaS3Service {
                     error!("Content-Length exceeds 5GB");
                     return Err(s3_error!(EntityTooLarge, "Content-Length larger than 5Gib"));
                 }
+                check_object_size(bytes as u64, self.max_object_size)?;
             }
         };
 
@@ -1640,3 +1686,258 @@ impl S3 for ArunaS3Service {
         Ok(resp)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caching::cache::tests::init_cache;
+    use crate::structs::{ObjectLocation, ObjectType, ResourceStates, UserState};
+    use anyhow::anyhow;
+    use async_channel::{Receiver, Sender};
+    use diesel_ulid::DieselUlid;
+
+    // Discards uploads and records deleted locations,
+    // unexpected backend calls fail the request instead of panicking
+    #[derive(Debug, Default)]
+    struct NoopBackend {
+        deleted: Arc<std::sync::Mutex<Vec<ObjectLocation>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl StorageBackend for NoopBackend {
+        async fn put_object(
+            &self,
+            recv: Receiver<Result<bytes::Bytes>>,
+            _location: ObjectLocation,
+            content_len: i64,
+        ) -> Result<()> {
+            let mut received = 0;
+            while received < content_len {
+                received += recv.recv().await??.len() as i64;
+            }
+            Ok(())
+        }
+        async fn get_object(
+            &self,
+            _location: ObjectLocation,
+            _range: Option<String>,
+            _sender: Sender<Result<bytes::Bytes, Box<dyn std::error::Error + Send + Sync>>>,
+        ) -> Result<()> {
+            Err(anyhow!("Not supported by NoopBackend"))
+        }
+        async fn head_object(&self, _location: ObjectLocation) -> Result<i64> {
+            Err(anyhow!("Not supported by NoopBackend"))
+        }
+        async fn init_multipart_upload(&self, _location: ObjectLocation) -> Result<String> {
+            Err(anyhow!("Not supported by NoopBackend"))
+        }
+        async fn upload_multi_object(
+            &self,
+            _recv: Receiver<Result<bytes::Bytes>>,
+            _location: ObjectLocation,
+            _upload_id: String,
+            _content_len: i64,
+            _part_number: i32,
+        ) -> Result<PartETag> {
+            Err(anyhow!("Not supported by NoopBackend"))
+        }
+        async fn finish_multipart_upload(
+            &self,
+            _location: ObjectLocation,
+            _parts: Vec<PartETag>,
+            _upload_id: String,
+        ) -> Result<()> {
+            Err(anyhow!("Not supported by NoopBackend"))
+        }
+        async fn create_bucket(&self, _bucket: String) -> Result<()> {
+            Err(anyhow!("Not supported by NoopBackend"))
+        }
+        async fn delete_object(&self, location: ObjectLocation) -> Result<()> {
+            self.deleted.lock().unwrap().push(location);
+            Ok(())
+        }
+        async fn initialize_location(
+            &self,
+            _obj: &ProxyObject,
+            expected_size: Option<i64>,
+            _names: [Option<(DieselUlid, String)>; 4],
+            _temp: bool,
+        ) -> Result<ObjectLocation> {
+            Ok(ObjectLocation {
+                id: DieselUlid::generate(),
+                raw_content_len: expected_size.unwrap_or_default(),
+                ..Default::default()
+            })
+        }
+    }
+
+    async fn head(
+        states: ResourceStates,
+        location: Option<ObjectLocation>,
+    ) -> S3Result<S3Response<HeadObjectOutput>> {
+        let backend: Arc<Box<dyn StorageBackend>> = Arc::new(Box::<NoopBackend>::default());
+        let service = ArunaS3Service::new(backend, init_cache().await)
+            .await
+            .unwrap();
+        let mut req = S3Request::new(
+            HeadObjectInput::builder()
+                .bucket("bucket".to_string())
+                .key("file.txt".to_string())
+                .build()
+                .unwrap(),
+        );
+        req.extensions.insert(CheckAccessResult::new(
+            ObjectsState::Regular { states, location },
+            UserState::default(),
+            None,
+        ));
+        service.head_object(req).await
+    }
+
+    #[tokio::test]
+    async fn test_head_object() {
+        let project = ProxyObject::initialize_now("bucket".to_string(), ObjectType::Project, None);
+        let object = ProxyObject::initialize_now(
+            "file.txt".to_string(),
+            ObjectType::Object,
+            Some(TypedRelation::Project(project.id)),
+        );
+        let mut states = ResourceStates::default();
+        states.set_project(project);
+        states.set_object(object.clone());
+        let location = ObjectLocation {
+            raw_content_len: 1234,
+            ..Default::default()
+        };
+
+        let output = head(states, Some(location)).await.unwrap().output;
+        assert_eq!(output.content_length, Some(1234));
+        assert_eq!(output.e_tag, Some(object.id.to_string()));
+        assert_eq!(
+            output.last_modified.map(time::OffsetDateTime::from),
+            Some(
+                time::OffsetDateTime::from_unix_timestamp((object.id.timestamp() / 1000) as i64)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            output.content_type.map(|m| m.essence_str().to_string()),
+            Some("text/plain".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_head_missing_object() {
+        let mut states = ResourceStates::default();
+        states.set_project(ProxyObject::initialize_now(
+            "bucket".to_string(),
+            ObjectType::Project,
+            None,
+        ));
+
+        let Err(err) = head(states, None).await else {
+            panic!("Expected NoSuchKey error")
+        };
+        assert_eq!(*err.code(), s3s::S3ErrorCode::NoSuchKey);
+        assert_eq!(err.status_code(), Some(http::StatusCode::NOT_FOUND));
+    }
+
+    async fn put(
+        states: ResourceStates,
+        body: &'static [u8],
+        content_md5: Option<String>,
+        content_length: i64,
+        max_object_size: Option<u64>,
+    ) -> (S3Result<S3Response<PutObjectOutput>>, Vec<ObjectLocation>) {
+        let backend = NoopBackend::default();
+        let deleted = backend.deleted.clone();
+        let backend: Arc<Box<dyn StorageBackend>> = Arc::new(Box::new(backend));
+        let mut service = ArunaS3Service::new(backend, init_cache().await)
+            .await
+            .unwrap();
+        service.max_object_size = max_object_size;
+        let mut req = S3Request::new(
+            PutObjectInput::builder()
+                .bucket("bucket".to_string())
+                .key("file.txt".to_string())
+                .content_length(Some(content_length))
+                .content_md5(content_md5)
+                .body(Some(StreamingBlob::from(s3s::Body::from(
+                    bytes::Bytes::from_static(body),
+                ))))
+                .build()
+                .unwrap(),
+        );
+        req.extensions.insert(CheckAccessResult::new(
+            ObjectsState::Regular {
+                states,
+                location: None,
+            },
+            UserState::default(),
+            None,
+        ));
+        let result = service.put_object(req).await;
+        let deleted = deleted.lock().unwrap().clone();
+        (result, deleted)
+    }
+
+    fn existing_object_states() -> ResourceStates {
+        let project = ProxyObject::initialize_now("bucket".to_string(), ObjectType::Project, None);
+        let mut object = ProxyObject::initialize_now(
+            "file.txt".to_string(),
+            ObjectType::Object,
+            Some(TypedRelation::Project(project.id)),
+        );
+        object.object_status = Status::Available;
+        let mut states = ResourceStates::default();
+        states.set_project(project);
+        states.set_object(object);
+        states
+    }
+
+    #[tokio::test]
+    async fn test_put_existing_object_md5_mismatch() {
+        let content_md5 = Some(general_purpose::STANDARD.encode(Md5::digest(b"other")));
+        let (result, deleted) =
+            put(existing_object_states(), b"content", content_md5, 7, None).await;
+
+        // Rejected before a new revision is requested from the server
+        let Err(err) = result else {
+            panic!("Expected BadDigest error")
+        };
+        assert_eq!(*err.code(), s3s::S3ErrorCode::BadDigest);
+        assert_eq!(deleted.len(), 1);
+
+        // Verified uploads request the revision, which fails without a server
+        let content_md5 = Some(general_purpose::STANDARD.encode(Md5::digest(b"content")));
+        let (result, deleted) =
+            put(existing_object_states(), b"content", content_md5, 7, None).await;
+        let Err(err) = result else {
+            panic!("Expected InternalError")
+        };
+        assert_eq!(*err.code(), s3s::S3ErrorCode::InternalError);
+        assert_eq!(deleted.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_put_object_too_large() {
+        // Announced sizes above the limit are rejected before reading the body
+        let (result, deleted) = put(existing_object_states(), b"content", None, 7, Some(4)).await;
+        let Err(err) = result else {
+            panic!("Expected EntityTooLarge error")
+        };
+        assert_eq!(*err.code(), s3s::S3ErrorCode::EntityTooLarge);
+        assert_eq!(err.status_code(), Some(http::StatusCode::PAYLOAD_TOO_LARGE));
+        assert!(deleted.is_empty());
+
+        // Bodies exceeding the limit despite a smaller announced size are aborted,
+        // the partial upload is deleted and no revision is requested
+        let (result, deleted) = put(existing_object_states(), b"content", None, 4, Some(4)).await;
+        let Err(err) = result else {
+            panic!("Expected EntityTooLarge error")
+        };
+        assert_eq!(*err.code(), s3s::S3ErrorCode::EntityTooLarge);
+        assert_eq!(err.status_code(), Some(http::StatusCode::PAYLOAD_TOO_LARGE));
+        assert_eq!(deleted.len(), 1);
+    }
+}
@@ -154,3 +154,90 @@ pub async fn list_response(
 
     Ok((keys, common_prefixes, new_continuation_token))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::caching::cache::tests::init_cache;
+    use crate::structs::TypedRelation;
+
+    // bucket/{dir/{f2,f3},file1}
+    async fn init_bucket() -> Arc<Cache> {
+        let cache = init_cache().await;
+        let project = Object::initialize_now("bucket".to_string(), ObjectType::Project, None);
+        let project_relation = Some(TypedRelation::Project(project.id));
+        let collection = Object::initialize_now(
+            "dir".to_string(),
+            ObjectType::Collection,
+            project_relation.clone(),
+        );
+        let collection_relation = Some(TypedRelation::Collection(collection.id));
+        let objects = [
+            Object::initialize_now("file1".to_string(), ObjectType::Object, project_relation),
+            Object::initialize_now(
+                "f2".to_string(),
+                ObjectType::Object,
+                collection_relation.clone(),
+            ),
+            Object::initialize_now("f3".to_string(), ObjectType::Object, collection_relation),
+        ];
+        cache.upsert_object(project).await.unwrap();
+        cache.upsert_object(collection).await.unwrap();
+        for object in objects {
+            cache.upsert_object(object).await.unwrap();
+        }
+        cache
+    }
+
+    fn key_names(keys: &BTreeSet<Contents>) -> Vec<&str> {
+        keys.iter().map(|c| c.key.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_prefix() {
+        let cache = init_bucket().await;
+        let (keys, common_prefixes, token) =
+            list_response(&cache, &None, &Some("dir/".to_string()), "bucket", "", 1000)
+                .await
+                .unwrap();
+        assert_eq!(key_names(&keys), vec!["dir/f2", "dir/f3"]);
+        assert!(common_prefixes.is_empty());
+        assert!(token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_delimiter() {
+        let cache = init_bucket().await;
+        let (keys, common_prefixes, token) =
+            list_response(&cache, &Some("/".to_string()), &None, "bucket", "", 1000)
+                .await
+                .unwrap();
+        assert_eq!(key_names(&keys), vec!["file1"]);
+        assert_eq!(common_prefixes, BTreeSet::from(["dir/".to_string()]));
+        assert!(token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_pagination() {
+        let cache = init_bucket().await;
+        let mut listed = Vec::new();
+        let mut start_at = String::new();
+        loop {
+            let (keys, _, token) = list_response(&cache, &None, &None, "bucket", &start_at, 1)
+                .await
+                .unwrap();
+            assert_eq!(keys.len(), 1);
+            listed.extend(keys.into_iter().map(|c| c.key));
+            match token {
+                Some(token) => {
+                    // Tokens are decoded the same way in list_objects_v2
+                    start_at =
+                        String::from_utf8(general_purpose::STANDARD_NO_PAD.decode(token).unwrap())
+                            .unwrap();
+                }
+                None => break,
+            }
+        }
+        assert_eq!(listed, vec!["dir/f2", "dir/f3", "file1"]);
+    }
+}
@@ -8,4 +8,4 @@ $Runtime run -d -p 9000:9000 -p 9001:9001 --name minio -e MINIO_DOMAIN=localhost
 psql "postgres://yugabyte@localhost:5433" -c 'CREATE DATABASE proxy' 
 
 # Import schema (script has to be called from project root)
-psql "postgres://yugabyte@localhost:5433/proxy" < $(pwd)/src/database/schema.sql
\ No newline at end of file
+psql "postgres://yugabyte@localhost:5433/proxy" < $(pwd)/src/database/migrations/001_initial.sql
\ No newline at end of file
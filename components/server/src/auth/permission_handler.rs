@@ -25,6 +25,38 @@ pub struct PermissionCheck {
     pub proxy_id: Option<DieselUlid>,
 }
 
+/// Permission check result that keeps missing resources apart from denials
+#[derive(Debug, PartialEq, Eq)]
+pub enum PermissionOutcome {
+    Granted(DieselUlid),
+    ResourceMissing(DieselUlid),
+    Denied,
+}
+
+impl PermissionOutcome {
+    fn from_check(
+        cache: &Cache,
+        ctxs: &[Context],
+        check: Result<PermissionCheck, tonic::Status>,
+    ) -> Self {
+        match check {
+            Ok(PermissionCheck { user_id, .. }) => PermissionOutcome::Granted(user_id),
+            // Only a completed check may reveal which resource is missing,
+            // e.g. invalid tokens are denied before resources are looked up
+            Err(status) if status.code() == tonic::Code::NotFound => ctxs
+                .iter()
+                .find_map(|ctx| match ctx.variant {
+                    ContextVariant::Resource((id, _)) if cache.get_object(&id).is_none() => {
+                        Some(PermissionOutcome::ResourceMissing(id))
+                    }
+                    _ => None,
+                })
+                .unwrap_or(PermissionOutcome::Denied),
+            Err(_) => PermissionOutcome::Denied,
+        }
+    }
+}
+
 impl PermissionHandler {
     pub fn new(cache: Arc<Cache>, token_handler: Arc<TokenHandler>) -> Self {
         Self {
@@ -65,8 +97,12 @@ impl PermissionHandler {
             }
         };
 
-        // dbg!(&processed_token);
-        // dbg!(&ctxs);
+        log::trace!(
+            "Checking {} contexts for {} (proxy: {})",
+            ctxs.len(),
+            main_id,
+            is_proxy
+        );
 
         // // Define rule
         // let program = Program::compile(
@@ -207,9 +243,18 @@ impl PermissionHandler {
                             | ContextVariant::Registered
                             | ContextVariant::GlobalProxy
                             | ContextVariant::Resource(_) => {}
-                            _ => return Err(tonic::Status::invalid_argument(
-                                "Only resource functionality allowed for Dataproxy signed tokens",
-                            )),
+                            _ => {
+                                // Keep a trail of rejected impersonation attempts
+                                log::warn!(
+                                    "Rejected impersonation of user {} by proxy {} for context {:?}",
+                                    main_id,
+                                    intent.target,
+                                    ctx.variant
+                                );
+                                return Err(tonic::Status::invalid_argument(
+                                    "Only resource functionality allowed for Dataproxy signed tokens",
+                                ));
+                            }
                         }
                     }
                 } else if intent.action == Action::FetchInfo {
@@ -235,11 +280,17 @@ impl PermissionHandler {
                             }
                         }
                     }
-                    //unimplemented!("Permission check for Dataproxy notification fetch not yet implemented")
                 }
 
+                // The proxy is registered, as its token was signed with a key associated
+                // with intent.target; all requested resources and users must be served by it
                 if self.cache.check_proxy_ctxs(&intent.target, &ctxs) {
-                    //dbg!(&intent.target);
+                    log::trace!(
+                        "Granted proxy {} access to {} contexts for {}",
+                        intent.target,
+                        ctxs.len(),
+                        main_id
+                    );
                     //Ok((main_id, token, true, Some(intent.target)))
                     Ok(PermissionCheck {
                         user_id: main_id,
@@ -248,6 +299,12 @@ impl PermissionHandler {
                         proxy_id: Some(intent.target),
                     })
                 } else {
+                    log::trace!(
+                        "Denied proxy {} access to {} contexts for {}",
+                        intent.target,
+                        ctxs.len(),
+                        main_id
+                    );
                     Err(tonic::Status::unauthenticated(
                         "Invalid proxy authentication",
                     ))
@@ -262,6 +319,7 @@ impl PermissionHandler {
             .cache
             .check_permissions_with_contexts(&ctxs, permissions, personal, &main_id)
         {
+            log::trace!("Granted access to {} contexts for {}", ctxs.len(), main_id);
             //Ok((main_id, token, false, None))
             Ok(PermissionCheck {
                 user_id: main_id,
@@ -269,8 +327,16 @@ impl PermissionHandler {
                 is_proxy,
                 proxy_id: None,
             })
+        } else if ctxs.iter().any(|ctx| match ctx.variant {
+            ContextVariant::Resource((id, _)) => self.cache.get_object(&id).is_none(),
+            _ => false,
+        }) {
+            log::trace!("Denied access to {} contexts for {}", ctxs.len(), main_id);
+            // Report missing resources instead of masking them as permission errors
+            Err(tonic::Status::not_found("Resource not found"))
         } else {
-            Err(tonic::Status::unauthenticated("Invalid permissions"))
+            log::trace!("Denied access to {} contexts for {}", ctxs.len(), main_id);
+            Err(tonic::Status::permission_denied("Invalid permissions"))
         }
     }
 
@@ -284,6 +350,23 @@ impl PermissionHandler {
         Ok(user_id)
     }
 
+    /// Returns whether the token has ADMIN permissions on the resource, e.g. to
+    /// allow the removal of static labels
+    pub async fn is_resource_admin(&self, token: &str, resource_id: DieselUlid) -> bool {
+        let ctx = Context::res_ctx(resource_id, DbPermissionLevel::ADMIN, true);
+        self.check_permissions(token, vec![ctx]).await.is_ok()
+    }
+
+    /// Like check_permissions, but reports which requested resource does not exist
+    pub async fn check_permissions_detailed(
+        &self,
+        token: &str,
+        ctxs: Vec<Context>,
+    ) -> PermissionOutcome {
+        let check = self.check_permissions_verbose(token, ctxs.clone()).await;
+        PermissionOutcome::from_check(&self.cache, &ctxs, check)
+    }
+
     pub async fn check_unregistered_oidc(&self, token: &str) -> Result<OIDCMapping> {
         let split = token
             .split('.')
@@ -310,3 +393,65 @@ impl PermissionHandler {
         Ok(mapping)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::dsls::object_dsl::ObjectWithRelations;
+    use crate::database::enums::ObjectType;
+
+    #[tokio::test]
+    async fn test_permission_outcome() {
+        let cache = Cache::new();
+        let object_id = DieselUlid::generate();
+        cache.add_object(ObjectWithRelations::random_object_v2(
+            &object_id,
+            ObjectType::OBJECT,
+            vec![],
+            vec![],
+        ));
+        let missing_id = DieselUlid::generate();
+        let user_id = DieselUlid::generate();
+        let existing = vec![Context::res_ctx(object_id, DbPermissionLevel::READ, true)];
+        let with_missing = vec![
+            Context::res_ctx(object_id, DbPermissionLevel::READ, true),
+            Context::res_ctx(missing_id, DbPermissionLevel::READ, true),
+        ];
+
+        let granted = Ok(PermissionCheck {
+            user_id,
+            token: None,
+            is_proxy: false,
+            proxy_id: None,
+        });
+        assert_eq!(
+            PermissionOutcome::from_check(&cache, &existing, granted),
+            PermissionOutcome::Granted(user_id)
+        );
+        assert_eq!(
+            PermissionOutcome::from_check(
+                &cache,
+                &with_missing,
+                Err(tonic::Status::not_found("Resource not found"))
+            ),
+            PermissionOutcome::ResourceMissing(missing_id)
+        );
+        assert_eq!(
+            PermissionOutcome::from_check(
+                &cache,
+                &existing,
+                Err(tonic::Status::permission_denied("Invalid permissions"))
+            ),
+            PermissionOutcome::Denied
+        );
+        // Failed authentication does not reveal missing resources
+        assert_eq!(
+            PermissionOutcome::from_check(
+                &cache,
+                &with_missing,
+                Err(tonic::Status::unauthenticated("Unauthorized"))
+            ),
+            PermissionOutcome::Denied
+        );
+    }
+}
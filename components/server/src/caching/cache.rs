@@ -52,39 +52,197 @@ use evmap::WriteHandle;
 use itertools::Itertools;
 use std::collections::VecDeque;
 use std::ops::Deref;
+use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+const DEFAULT_PERMISSION_PATH_CACHE_SIZE: usize = 10_000;
+const DEFAULT_PERMISSION_TRAVERSAL_LIMIT: usize = 100_000;
+
+/// Memoized (ancestor, resource) permission paths. Every invalidation draws a
+/// new stamp, a path is only valid if its traversal started after the last
+/// invalidation of its ancestor.
+struct PermissionPaths {
+    capacity: usize,
+    stamp: AtomicU64,
+    cleared: AtomicU64,
+    // Drives the least recently used eviction
+    clock: AtomicU64,
+    invalidated: DashMap<DieselUlid, u64, RandomState>,
+    // (ancestor, resource) -> (stamp, reachable, last used)
+    paths: DashMap<(DieselUlid, DieselUlid), (u64, bool, AtomicU64), RandomState>,
+}
+
+impl PermissionPaths {
+    fn new(capacity: usize) -> Self {
+        PermissionPaths {
+            capacity,
+            stamp: AtomicU64::new(0),
+            cleared: AtomicU64::new(0),
+            clock: AtomicU64::new(0),
+            invalidated: DashMap::default(),
+            paths: DashMap::default(),
+        }
+    }
+
+    fn stamp(&self) -> u64 {
+        self.stamp.load(Ordering::SeqCst)
+    }
+
+    fn next_stamp(&self) -> u64 {
+        self.stamp.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn is_valid(&self, ancestor: &DieselUlid, stamp: u64) -> bool {
+        stamp >= self.cleared.load(Ordering::SeqCst)
+            && self
+                .invalidated
+                .get(ancestor)
+                .is_none_or(|invalidated| stamp >= *invalidated)
+    }
+
+    fn get(&self, ancestor: DieselUlid, resource: DieselUlid) -> Option<bool> {
+        let path = self.paths.get(&(ancestor, resource))?;
+        let (stamp, reachable, used) = path.value();
+        if !self.is_valid(&ancestor, *stamp) {
+            return None;
+        }
+        used.store(self.tick(), Ordering::Relaxed);
+        Some(*reachable)
+    }
+
+    fn insert(&self, ancestor: DieselUlid, resource: DieselUlid, stamp: u64, reachable: bool) {
+        if !self.is_valid(&ancestor, stamp) {
+            return;
+        }
+        if self.paths.len() >= self.capacity {
+            // Drop outdated paths first and evict the least recently used
+            // quarter if that is not enough
+            self.paths
+                .retain(|(ancestor, _), (stamp, _, _)| self.is_valid(ancestor, *stamp));
+            self.evict(self.capacity - self.capacity / 4);
+            self.prune_invalidated();
+        }
+        self.paths.insert(
+            (ancestor, resource),
+            (stamp, reachable, AtomicU64::new(self.tick())),
+        );
+    }
+
+    /// Evicts the least recently used paths until at most `keep` are left
+    fn evict(&self, keep: usize) {
+        let mut used = self
+            .paths
+            .iter()
+            .map(|path| path.value().2.load(Ordering::Relaxed))
+            .collect::<Vec<_>>();
+        let evict = used.len().saturating_sub(keep);
+        if evict == 0 {
+            return;
+        }
+        let (_, threshold, _) = used.select_nth_unstable(evict - 1);
+        let threshold = *threshold;
+        self.paths
+            .retain(|_, (_, _, used)| used.load(Ordering::Relaxed) > threshold);
+    }
+
+    /// Drops invalidations that no stored path predates. Traversals that
+    /// started before them are rejected by raising `cleared` instead.
+    fn prune_invalidated(&self) {
+        let oldest = self
+            .paths
+            .iter()
+            .map(|path| path.value().0)
+            .min()
+            .unwrap_or_else(|| self.stamp());
+        let mut pruned = 0;
+        self.invalidated.retain(|_, invalidated| {
+            if *invalidated <= oldest {
+                pruned = pruned.max(*invalidated);
+                false
+            } else {
+                true
+            }
+        });
+        self.cleared.fetch_max(pruned, Ordering::SeqCst);
+    }
+
+    fn invalidate(&self, ancestors: impl IntoIterator<Item = DieselUlid>) {
+        for ancestor in ancestors {
+            self.invalidated.insert(ancestor, self.next_stamp());
+        }
+        if self.invalidated.len() >= self.capacity {
+            self.prune_invalidated();
+        }
+    }
+
+    fn clear(&self) {
+        self.cleared.store(self.next_stamp(), Ordering::SeqCst);
+        self.invalidated.clear();
+        self.paths.clear();
+    }
+}
+
+/// Parses the env variable `key`, falls back to `default` if it is unset or invalid
+fn env_or<T: FromStr>(key: &str, default: T) -> T {
+    dotenvy::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
 pub struct Cache {
     object_cache: DashMap<DieselUlid, ObjectWithRelations, RandomState>,
     stats_reader: ReadHandleFactory<DieselUlid, CopyValue<ObjectStats>>, //RwLock<ReadHandle<DieselUlid, ObjectStats>>,
     stats_writer: Arc<Mutex<WriteHandle<DieselUlid, CopyValue<ObjectStats>>>>,
     user_cache: DashMap<DieselUlid, User, RandomState>,
+    oidc_mappings: DashMap<OIDCMapping, DieselUlid, RandomState>,
     pubkeys: DashMap<i16, PubKeyEnum, RandomState>,
     issuer_info: DashMap<String, Issuer>,
     pub issuer_sender: Sender<String>,
     lock: AtomicBool,
     object_rules: DashMap<DieselUlid, Arc<CachedRule>>,
     object_rule_bindings: DashMap<DieselUlid, Arc<Vec<RuleBinding>>, RandomState>,
+    // (ancestor, resource) -> resource is part of the ancestors permission subtree
+    permission_paths: PermissionPaths,
+    // Max. resources visited per permission traversal
+    traversal_limit: usize,
 }
 
 impl Cache {
     pub fn new() -> Arc<Self> {
         let (issuer_sender, issuer_recv) = async_channel::bounded(50);
         let (stats_reader, stats_writer) = evmap::new();
+        let permission_path_cache_size = env_or(
+            "PERMISSION_PATH_CACHE_SIZE",
+            DEFAULT_PERMISSION_PATH_CACHE_SIZE,
+        );
+        let traversal_limit = env_or(
+            "PERMISSION_TRAVERSAL_LIMIT",
+            DEFAULT_PERMISSION_TRAVERSAL_LIMIT,
+        );
 
         let cache = Arc::new(Self {
             object_cache: DashMap::default(),
             stats_reader: stats_reader.factory(),
             stats_writer: Arc::new(Mutex::new(stats_writer)),
             user_cache: DashMap::default(),
+            oidc_mappings: DashMap::default(),
             pubkeys: DashMap::default(),
             issuer_info: DashMap::default(),
             issuer_sender,
             lock: AtomicBool::new(false),
             object_rules: DashMap::default(),
             object_rule_bindings: DashMap::default(),
+            permission_paths: PermissionPaths::new(permission_path_cache_size),
+            traversal_limit,
         });
 
         let cache_clone = cache.clone();
@@ -102,6 +260,7 @@ impl Cache {
         self.lock.store(true, std::sync::atomic::Ordering::Relaxed);
         self.object_cache.clear();
         self.user_cache.clear();
+        self.oidc_mappings.clear();
         self.pubkeys.clear();
         let client = db.get_client().await?;
 
@@ -109,6 +268,7 @@ impl Cache {
         for obj in all_objects {
             self.object_cache.insert(obj.object.id, obj);
         }
+        self.permission_paths.clear();
 
         // Object stats update
         let mut stats_writer = self.stats_writer.lock().await;
@@ -121,6 +281,7 @@ impl Cache {
 
         let users = User::all(&client).await?;
         for user in users {
+            self.index_oidc_mappings(&user);
             self.user_cache.insert(user.id, user);
         }
 
@@ -378,7 +539,10 @@ impl Cache {
 
     pub fn insert_object(&self, object: ObjectWithRelations) {
         self.check_lock();
-        self.object_cache.insert(object.object.id, object);
+        let id = object.object.id;
+        let ancestors = self.permission_ancestors(&id);
+        self.object_cache.insert(id, object);
+        self.invalidate_permission_paths(ancestors, &id);
     }
 
     pub fn get_user(&self, id: &DieselUlid) -> Option<User> {
@@ -433,11 +597,13 @@ impl Cache {
 
     pub fn upsert_object(&self, id: &DieselUlid, object: ObjectWithRelations) {
         self.check_lock();
+        let ancestors = self.permission_ancestors(id);
         if let Some(mut x) = self.object_cache.get_mut(id) {
             *x.value_mut() = object;
         } else {
             self.object_cache.insert(object.object.id, object);
         }
+        self.invalidate_permission_paths(ancestors, id);
     }
 
     pub async fn upsert_object_stats(&self, object_stats: Vec<ObjectStats>) -> Result<()> {
@@ -485,18 +651,56 @@ impl Cache {
                 *target.value_mut() = clone;
             }
         }
+        for relation in &relations {
+            self.invalidate_permission_paths(HashSet::default(), &relation.origin_pid);
+        }
     }
 
     pub fn update_user(&self, id: &DieselUlid, user: User) {
         self.check_lock();
         if let Some(mut x) = self.user_cache.get_mut(id) {
+            self.unindex_oidc_mappings(x.value());
+            self.index_oidc_mappings(&user);
             *x.value_mut() = user;
         }
     }
 
     pub fn add_object(&self, rel: ObjectWithRelations) {
         self.check_lock();
-        self.object_cache.insert(rel.object.id, rel);
+        let id = rel.object.id;
+        let ancestors = self.permission_ancestors(&id);
+        self.object_cache.insert(id, rel);
+        self.invalidate_permission_paths(ancestors, &id);
+    }
+
+    /// Collects `id` and all cached resources with `id` in their permission subtree
+    fn permission_ancestors(&self, id: &DieselUlid) -> HashSet<DieselUlid> {
+        let mut ancestors = HashSet::default();
+        let mut queue = VecDeque::from([*id]);
+        while let Some(current) = queue.pop_front() {
+            if !ancestors.insert(current) {
+                continue;
+            }
+            if let Some(object) = self.object_cache.get(&current) {
+                queue.extend(object.get_parents());
+                queue.extend(
+                    object
+                        .inbound
+                        .0
+                        .iter()
+                        .filter(|relation| relation.value().relation_name == "DELETED")
+                        .map(|relation| relation.value().origin_pid),
+                );
+            }
+        }
+        ancestors
+    }
+
+    /// Drops the memoized permission paths of all resources that contained
+    /// `id` in their subtree before (`ancestors`) or after a change of `id`
+    fn invalidate_permission_paths(&self, mut ancestors: HashSet<DieselUlid>, id: &DieselUlid) {
+        ancestors.extend(self.permission_ancestors(id));
+        self.permission_paths.invalidate(ancestors);
     }
 
     pub fn remove_object(&self, id: &DieselUlid) {
@@ -508,7 +712,10 @@ impl Cache {
 
     pub fn add_user(&self, id: DieselUlid, user: User) {
         self.check_lock();
-        self.user_cache.insert(id, user);
+        if let Some(old) = self.user_cache.insert(id, user.clone()) {
+            self.unindex_oidc_mappings(&old);
+        }
+        self.index_oidc_mappings(&user);
     }
 
     pub fn add_pubkey(&self, id: i16, key: PubKeyEnum) {
@@ -521,6 +728,11 @@ impl Cache {
         self.pubkeys.remove(&id);
     }
 
+    pub fn add_issuer(&self, issuer: Issuer) {
+        self.check_lock();
+        self.issuer_info.insert(issuer.issuer_name.clone(), issuer);
+    }
+
     pub fn get_issuer(&self, kid: &str) -> Option<Ref<'_, String, Issuer>> {
         self.check_lock();
         self.issuer_info.get(kid)
@@ -528,15 +740,28 @@ impl Cache {
 
     pub fn remove_user(&self, id: &DieselUlid) {
         self.check_lock();
-        self.user_cache.remove(id);
+        if let Some((_, user)) = self.user_cache.remove(id) {
+            self.unindex_oidc_mappings(&user);
+        }
     }
 
     pub fn get_user_by_oidc(&self, external: &OIDCMapping) -> Option<User> {
         self.check_lock();
-        self.user_cache
-            .iter()
-            .find(|x| x.value().attributes.0.external_ids.contains(external))
-            .map(|x| x.value().clone())
+        let user_id = *self.oidc_mappings.get(external)?.value();
+        self.user_cache.get(&user_id).map(|x| x.value().clone())
+    }
+
+    fn index_oidc_mappings(&self, user: &User) {
+        for mapping in &user.attributes.0.external_ids {
+            self.oidc_mappings.insert(mapping.clone(), user.id);
+        }
+    }
+
+    fn unindex_oidc_mappings(&self, user: &User) {
+        for mapping in &user.attributes.0.external_ids {
+            self.oidc_mappings
+                .remove_if(mapping, |_, user_id| user_id == &user.id);
+        }
     }
 
     pub async fn get_all_users(&self) -> Vec<APIUser> {
@@ -725,31 +950,81 @@ impl Cache {
         id: &DieselUlid,
         perm: DbPermissionLevel,
         ctxs: &mut HashMap<DieselUlid, DbPermissionLevel>,
+    ) -> Result<bool> {
+        self.bounded_traverse_down(id, perm, ctxs, self.traversal_limit)
+    }
+
+    fn bounded_traverse_down(
+        &self,
+        id: &DieselUlid,
+        perm: DbPermissionLevel,
+        ctxs: &mut HashMap<DieselUlid, DbPermissionLevel>,
+        limit: usize,
     ) -> Result<bool> {
         self.check_lock();
         if ctxs.is_empty() {
             return Ok(true);
         }
 
+        // Resolve resources with already known paths from this ancestor first
+        let mut unreachable = Vec::new();
+        let mut invalid = false;
+        let stamp = self.permission_paths.stamp();
+        ctxs.retain(
+            |resource, needed| match self.permission_paths.get(*id, *resource) {
+                Some(true) => {
+                    invalid |= *needed > perm;
+                    false
+                }
+                Some(false) => {
+                    unreachable.push((*resource, *needed));
+                    false
+                }
+                None => true,
+            },
+        );
+        if invalid {
+            bail!("Invalid permissions")
+        }
+
+        let mut found = Vec::new();
         let mut queue = VecDeque::new();
-        queue.push_back(*id);
+        if !ctxs.is_empty() {
+            queue.push_back(*id);
+        }
 
+        let mut visited = 0;
         while let Some(x) = queue.pop_front() {
+            visited += 1;
+            if visited > limit {
+                bail!("Permission traversal exceeded limit of {} resources", limit)
+            }
             if let Some(x) = self.get_object(&x) {
                 for child in x.get_permission_children() {
                     if let Some(got_perm) = ctxs.remove(&child) {
+                        found.push(child);
                         if got_perm > perm {
                             bail!("Invalid permissions")
                         }
                         if ctxs.is_empty() {
-                            return Ok(true);
+                            queue.clear();
+                            break;
                         }
                     }
                     queue.push_back(child);
                 }
             }
         }
-        Ok(false)
+
+        // Remember the outcome and hand unresolved resources back to the caller
+        for resource in found {
+            self.permission_paths.insert(*id, resource, stamp, true);
+        }
+        for resource in ctxs.keys() {
+            self.permission_paths.insert(*id, *resource, stamp, false);
+        }
+        ctxs.extend(unreachable);
+        Ok(ctxs.is_empty())
     }
 
     pub fn get_subresources(&self, root_id: &DieselUlid) -> Result<Vec<DieselUlid>> {
@@ -874,17 +1149,34 @@ impl Cache {
         ProxyCacheIterator::new(object_cache, user, pubkeys, *endpoint_id, cache)
     }
 
+    /// Checks if the endpoint still holds data of objects created by the user
+    pub fn has_user_data_on_endpoint(
+        &self,
+        user_id: &DieselUlid,
+        endpoint_id: &DieselUlid,
+    ) -> bool {
+        self.check_lock();
+        self.object_cache.iter().any(|entry| {
+            let object = &entry.object;
+            object.object_type == ObjectType::OBJECT
+                && object.object_status != ObjectStatus::DELETED
+                && object.created_by == *user_id
+                && object.endpoints.0.contains_key(endpoint_id)
+        })
+    }
+
     pub fn oidc_mapping_exists(&self, mapping: &OIDCMapping) -> bool {
         self.check_lock();
-        self.user_cache
-            .iter()
-            .any(|x| x.value().attributes.0.external_ids.contains(mapping))
+        self.oidc_mappings.contains_key(mapping)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::dsls::object_dsl::EndpointInfo;
+    use crate::database::enums::{ReplicationStatus, ReplicationType};
+    use crate::middlelayer::workspace_request_types::CreateWorkspace;
 
     #[tokio::test]
     async fn test_remove_object() {
@@ -909,6 +1201,99 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_has_user_data_on_endpoint() {
+        let cache = Cache::new();
+        let object_ulid = DieselUlid::generate();
+        let object_plus =
+            ObjectWithRelations::random_object_v2(&object_ulid, ObjectType::OBJECT, vec![], vec![]);
+        let user_id = object_plus.object.created_by;
+        let endpoint_id = DieselUlid::generate();
+        object_plus.object.endpoints.0.insert(
+            endpoint_id,
+            EndpointInfo {
+                replication: ReplicationType::FullSync,
+                status: Some(ReplicationStatus::Finished),
+            },
+        );
+        cache.add_object(object_plus);
+
+        assert!(cache.has_user_data_on_endpoint(&user_id, &endpoint_id));
+        assert!(!cache.has_user_data_on_endpoint(&user_id, &DieselUlid::generate()));
+        assert!(!cache.has_user_data_on_endpoint(&DieselUlid::generate(), &endpoint_id));
+
+        // Deleted objects do not block endpoint removal
+        cache.remove_object(&object_ulid);
+        assert!(!cache.has_user_data_on_endpoint(&user_id, &endpoint_id));
+    }
+
+    #[tokio::test]
+    async fn test_check_proxy_ctxs() {
+        let cache = Cache::new();
+        let endpoint_id = DieselUlid::generate();
+        let object_ulid = DieselUlid::generate();
+        let object_plus =
+            ObjectWithRelations::random_object_v2(&object_ulid, ObjectType::OBJECT, vec![], vec![]);
+        object_plus.object.endpoints.0.insert(
+            endpoint_id,
+            EndpointInfo {
+                replication: ReplicationType::FullSync,
+                status: Some(ReplicationStatus::Finished),
+            },
+        );
+        cache.add_object(object_plus);
+
+        // Info fetch for resources on the proxy's own endpoint
+        let ctxs = vec![
+            Context::res_ctx(object_ulid, DbPermissionLevel::READ, true),
+            Context::proxy(),
+        ];
+        assert!(cache.check_proxy_ctxs(&endpoint_id, &ctxs));
+
+        // Resources of other endpoints and unknown resources are denied
+        assert!(!cache.check_proxy_ctxs(&DieselUlid::generate(), &ctxs));
+        assert!(!cache.check_proxy_ctxs(
+            &endpoint_id,
+            &[Context::res_ctx(
+                DieselUlid::generate(),
+                DbPermissionLevel::READ,
+                true
+            )]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_user_by_oidc() {
+        let cache = Cache::new();
+        let mapping = OIDCMapping {
+            external_id: "subject".to_string(),
+            oidc_name: "issuer".to_string(),
+        };
+        let unknown = OIDCMapping {
+            external_id: "unknown".to_string(),
+            oidc_name: "issuer".to_string(),
+        };
+        let mut user = CreateWorkspace::create_service_account(vec![], DieselUlid::generate());
+        user.attributes.0.external_ids = vec![mapping.clone()];
+
+        // Known subject resolves to user, unknown subject does not
+        cache.add_user(user.id, user.clone());
+        assert_eq!(cache.get_user_by_oidc(&mapping).unwrap().id, user.id);
+        assert!(cache.oidc_mapping_exists(&mapping));
+        assert!(cache.get_user_by_oidc(&unknown).is_none());
+
+        // Mapping follows user updates
+        let mut updated = user.clone();
+        updated.attributes.0.external_ids = vec![unknown.clone()];
+        cache.update_user(&user.id, updated);
+        assert!(cache.get_user_by_oidc(&mapping).is_none());
+        assert_eq!(cache.get_user_by_oidc(&unknown).unwrap().id, user.id);
+
+        // Mapping is dropped with the user
+        cache.remove_user(&user.id);
+        assert!(!cache.oidc_mapping_exists(&unknown));
+    }
+
     #[tokio::test]
     async fn test_traverse_down_with_relations() {
         let cache = Cache::new();
@@ -945,6 +1330,150 @@ mod tests {
         assert_eq!(result.unwrap_err().to_string(), "Invalid permissions");
     }
 
+    #[tokio::test]
+    async fn test_traverse_down_limit() {
+        let cache = Cache::new();
+        let root = DieselUlid::generate();
+        let requested = DieselUlid::generate();
+
+        let wide = ObjectWithRelations::random_object_to(&root, &DieselUlid::generate());
+        for _ in 0..20 {
+            wide.outbound_belongs_to
+                .0
+                .insert(DieselUlid::generate(), InternalRelation::default());
+        }
+        cache.add_object(wide);
+
+        // Walking the whole graph stays within a large enough budget
+        let mut ctxs = HashMap::from_iter([(requested, DbPermissionLevel::READ)]);
+        assert!(!cache
+            .bounded_traverse_down(&root, DbPermissionLevel::READ, &mut ctxs, 100)
+            .unwrap());
+
+        // Exceeding the budget aborts the traversal
+        cache.permission_paths.clear();
+        let mut ctxs = HashMap::from_iter([(requested, DbPermissionLevel::READ)]);
+        let result = cache.bounded_traverse_down(&root, DbPermissionLevel::READ, &mut ctxs, 10);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Permission traversal exceeded limit of 10 resources"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_traverse_down_memoized() {
+        let cache = Cache::new();
+        let id1 = DieselUlid::generate();
+        let id2 = DieselUlid::generate();
+        let id3 = DieselUlid::generate();
+        let id4 = DieselUlid::generate();
+
+        cache.add_object(ObjectWithRelations::random_object_to(&id1, &id2));
+        cache.add_object(ObjectWithRelations::random_object_to(&id2, &id3));
+
+        let mut ctxs = HashMap::from_iter([(id3, DbPermissionLevel::READ)]);
+        assert!(cache
+            .traverse_down(&id1, DbPermissionLevel::READ, &mut ctxs)
+            .unwrap());
+        assert_eq!(cache.permission_paths.get(id1, id3), Some(true));
+
+        // Repeated checks are answered from the memoized path without walking the graph
+        cache.object_cache.remove(&id2);
+        let mut ctxs = HashMap::from_iter([(id3, DbPermissionLevel::READ)]);
+        assert!(cache
+            .traverse_down(&id1, DbPermissionLevel::READ, &mut ctxs)
+            .unwrap());
+        let mut ctxs = HashMap::from_iter([(id3, DbPermissionLevel::ADMIN)]);
+        assert!(cache
+            .traverse_down(&id1, DbPermissionLevel::READ, &mut ctxs)
+            .is_err());
+
+        // Relation changes invalidate memoized paths
+        cache.upsert_object(&id1, ObjectWithRelations::random_object_to(&id1, &id4));
+        assert_eq!(cache.permission_paths.get(id1, id3), None);
+        let mut ctxs = HashMap::from_iter([(id3, DbPermissionLevel::READ)]);
+        assert!(!cache
+            .traverse_down(&id1, DbPermissionLevel::READ, &mut ctxs)
+            .unwrap());
+        assert!(ctxs.contains_key(&id3));
+        assert_eq!(cache.permission_paths.get(id1, id3), Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_traverse_down_invalidates_ancestors() {
+        let cache = Cache::new();
+        let project_a = DieselUlid::generate();
+        let child_a = DieselUlid::generate();
+        let project_b = DieselUlid::generate();
+        let child_b = DieselUlid::generate();
+
+        for (project, child) in [(project_a, child_a), (project_b, child_b)] {
+            cache.add_object(ObjectWithRelations::random_object_to(&project, &child));
+            let object = ObjectWithRelations::random_object_to(&child, &DieselUlid::generate());
+            object
+                .inbound_belongs_to
+                .0
+                .insert(project, InternalRelation::default());
+            cache.add_object(object);
+
+            let mut ctxs = HashMap::from_iter([(child, DbPermissionLevel::READ)]);
+            assert!(cache
+                .traverse_down(&project, DbPermissionLevel::READ, &mut ctxs)
+                .unwrap());
+        }
+
+        // Updating a resource only drops the paths of its ancestors
+        cache.upsert_object(&child_a, cache.get_object(&child_a).unwrap());
+        assert_eq!(cache.permission_paths.get(project_a, child_a), None);
+        assert_eq!(cache.permission_paths.get(project_b, child_b), Some(true));
+
+        // Full syncs drop everything
+        cache.permission_paths.clear();
+        assert_eq!(cache.permission_paths.get(project_b, child_b), None);
+    }
+
+    #[test]
+    fn test_permission_paths_evict_least_recently_used() {
+        let paths = PermissionPaths::new(4);
+        let ancestor = DieselUlid::generate();
+        let resources = (0..5).map(|_| DieselUlid::generate()).collect::<Vec<_>>();
+        for resource in &resources[..4] {
+            paths.insert(ancestor, *resource, paths.stamp(), true);
+        }
+
+        // Touched paths survive, only the least recently used one is evicted
+        assert_eq!(paths.get(ancestor, resources[0]), Some(true));
+        paths.insert(ancestor, resources[4], paths.stamp(), true);
+        assert_eq!(paths.paths.len(), 4);
+        assert_eq!(paths.get(ancestor, resources[0]), Some(true));
+        assert_eq!(paths.get(ancestor, resources[1]), None);
+        for resource in &resources[2..] {
+            assert_eq!(paths.get(ancestor, *resource), Some(true));
+        }
+    }
+
+    #[test]
+    fn test_permission_paths_prune_invalidated() {
+        let paths = PermissionPaths::new(2);
+        let ancestor = DieselUlid::generate();
+        let resource = DieselUlid::generate();
+        let stale = paths.stamp();
+        let pruned = DieselUlid::generate();
+        let kept = DieselUlid::generate();
+        paths.invalidate([pruned]);
+        paths.insert(ancestor, resource, paths.stamp(), true);
+
+        // The stored path postdates the first invalidation, so only the second is kept
+        paths.invalidate([kept]);
+        assert!(!paths.invalidated.contains_key(&pruned));
+        assert!(paths.invalidated.contains_key(&kept));
+        assert_eq!(paths.get(ancestor, resource), Some(true));
+
+        // Traversals started before a pruned invalidation stay rejected
+        paths.insert(DieselUlid::generate(), resource, stale, true);
+        assert_eq!(paths.paths.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_upstream_dfs_001() {
         // Init new cache
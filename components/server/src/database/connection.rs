@@ -1,6 +1,8 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use deadpool_postgres::{Config, ManagerConfig, Object, Pool, RecyclingMethod, Runtime};
-use tokio_postgres::NoTls;
+use diesel_ulid::DieselUlid;
+use itertools::Itertools;
+use tokio_postgres::{Client, NoTls};
 
 pub struct Database {
     connection_pool: Pool,
@@ -41,6 +43,7 @@ impl Database {
 
         dotenvy::from_filename(".env")?;
         let initial = tokio::fs::read_to_string(dotenvy::var("DATABASE_SCHEMA")?).await?;
+        check_unique_emails(&client).await?;
         client.batch_execute(&initial).await?;
         Ok(())
     }
@@ -49,3 +52,41 @@ impl Database {
         Ok(self.connection_pool.get().await?)
     }
 }
+
+/// The schema creates a unique index on lower(email), which fails while
+/// existing users share an email that only differs in case. Names the
+/// conflicting users instead of failing on the index creation.
+pub async fn check_unique_emails(client: &Client) -> Result<()> {
+    let needs_check: bool = client
+        .query_one(
+            "SELECT to_regclass('users') IS NOT NULL AND to_regclass('users_email_idx') IS NULL;",
+            &[],
+        )
+        .await?
+        .get(0);
+    if !needs_check {
+        return Ok(());
+    }
+
+    let duplicates = client
+        .query(
+            "SELECT lower(email), array_agg(id ORDER BY id) FROM users
+            WHERE email <> '' GROUP BY lower(email) HAVING count(*) > 1;",
+            &[],
+        )
+        .await?
+        .iter()
+        .map(|row| {
+            let email: String = row.get(0);
+            let ids: Vec<DieselUlid> = row.get(1);
+            format!("{} ({})", email, ids.iter().join(", "))
+        })
+        .collect::<Vec<_>>();
+    if !duplicates.is_empty() {
+        bail!(
+            "Users share emails that only differ in case, change them before starting the server: {}",
+            duplicates.join("; ")
+        )
+    }
+    Ok(())
+}
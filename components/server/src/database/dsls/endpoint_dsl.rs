@@ -1,13 +1,21 @@
+use crate::database::connection::Database;
 use crate::database::crud::{CrudDb, PrimaryKey};
 use crate::database::enums::{DataProxyFeature, EndpointStatus, EndpointVariant};
 use anyhow::Result;
 use diesel_ulid::DieselUlid;
 use itertools::Itertools;
+use log::error;
 use postgres_from_row::FromRow;
 use postgres_types::Json;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{Instant, MissedTickBehavior};
 use tokio_postgres::Client;
+use tonic::transport::{Channel, ClientTlsConfig};
+
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(FromRow, Debug, Clone)]
 pub struct Endpoint {
@@ -95,6 +103,99 @@ impl Endpoint {
         client.execute(&prepared, &[&id]).await?;
         Ok(())
     }
+    pub async fn update_status(
+        id: &DieselUlid,
+        status: EndpointStatus,
+        client: &Client,
+    ) -> Result<()> {
+        let query = "UPDATE endpoints SET status = $2 WHERE id = $1;";
+        let prepared = client.prepare(query).await?;
+        client.execute(&prepared, &[&id, &status]).await?;
+        Ok(())
+    }
+
+    /// Probes all gRPC hosts of the endpoint. Returns `None` if the endpoint
+    /// is in maintenance or has no gRPC host to probe.
+    pub async fn probe_status(&self) -> Option<EndpointStatus> {
+        if self.status == EndpointStatus::MAINTENANCE {
+            return None;
+        }
+        let mut total = 0;
+        let mut reachable = 0;
+        for config in &self.host_config.0 .0 {
+            if config.feature != DataProxyFeature::GRPC {
+                continue;
+            }
+            total += 1;
+            if probe_host(&config.url).await.is_ok() {
+                reachable += 1;
+            }
+        }
+        match (reachable, total) {
+            (_, 0) => None,
+            (0, _) => Some(EndpointStatus::UNAVAILABLE),
+            (r, t) if r < t => Some(EndpointStatus::DEGRADED),
+            _ => Some(EndpointStatus::AVAILABLE),
+        }
+    }
+
+    /// Probes the endpoint and persists its status if it changed
+    pub async fn refresh_status(&mut self, client: &Client) -> Result<()> {
+        if let Some(status) = self.probe_status().await {
+            if status != self.status {
+                Endpoint::update_status(&self.id, status, client).await?;
+                self.status = status;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn probe_host(url: &str) -> Result<()> {
+    let mut endpoint = Channel::from_shared(url.to_string())?
+        .connect_timeout(HEALTH_PROBE_TIMEOUT)
+        .timeout(HEALTH_PROBE_TIMEOUT);
+    if url.starts_with("https") {
+        endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+    }
+    endpoint.connect().await?;
+    Ok(())
+}
+
+/// Periodically probes all registered endpoints and updates their status
+///
+/// Panics if `interval` is zero
+pub async fn start_health_loop(database: Arc<Database>, interval: Duration) {
+    tokio::spawn(async move {
+        // Slow probes delay the next round instead of firing the missed ones at once
+        let mut ticks = tokio::time::interval_at(Instant::now() + interval, interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            let client = match database.get_client().await {
+                Ok(client) => client,
+                Err(err) => {
+                    error!("Failed to get database client for endpoint health: {}", err);
+                    continue;
+                }
+            };
+            let endpoints = match Endpoint::all(&client).await {
+                Ok(endpoints) => endpoints,
+                Err(err) => {
+                    error!("Failed to fetch endpoints for health check: {}", err);
+                    continue;
+                }
+            };
+            for mut endpoint in endpoints {
+                if let Err(err) = endpoint.refresh_status(&client).await {
+                    error!(
+                        "Failed to update status of endpoint {}: {}",
+                        endpoint.id, err
+                    );
+                }
+            }
+        }
+    });
 }
 impl Eq for Endpoint {}
 impl PartialEq for Endpoint {
@@ -94,6 +94,17 @@ pub struct Author {
     pub user_id: Option<DieselUlid>,
 }
 
+impl Author {
+    /// Authors linked to a user are identical if their user ids match,
+    /// all others have to match completely
+    pub fn is_same_author(&self, other: &Author) -> bool {
+        match (&self.user_id, &other.user_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => self == other,
+        }
+    }
+}
+
 #[derive(FromRow, FromSql, Debug, Clone, ToSql)]
 pub struct Object {
     pub id: DieselUlid,
@@ -800,6 +811,24 @@ impl Object {
         }
     }
 
+    /// Overall replication status of all data locations of this object
+    pub fn get_sync_status(&self) -> Option<ReplicationStatus> {
+        let statuses: Vec<ReplicationStatus> =
+            self.endpoints.0.iter().filter_map(|ep| ep.status).collect();
+        ReplicationStatus::aggregate(&statuses)
+    }
+
+    /// Data can only be downloaded if the object is available and at least one
+    /// endpoint holds a finished copy
+    pub fn is_downloadable(&self) -> bool {
+        self.object_status == ObjectStatus::AVAILABLE
+            && self
+                .endpoints
+                .0
+                .iter()
+                .any(|ep| ep.status == Some(ReplicationStatus::Finished))
+    }
+
     //ToDo: Docs
     pub async fn archive(ids: &Vec<DieselUlid>, client: &Client) -> Result<Vec<Object>> {
         let query_one = " WITH o AS 
@@ -859,6 +888,18 @@ impl Object {
             .collect())
     }
 
+    /// Fetches all non-deleted objects containing the provided hash, e.g. to detect duplicates
+    pub async fn get_objects_by_hash(hash: &Hash, client: &Client) -> Result<Vec<Object>> {
+        let query = "SELECT * FROM objects WHERE hashes @> $1 AND object_status != 'DELETED';";
+        let prepared = client.prepare(query).await?;
+        Ok(client
+            .query(&prepared, &[&Json(Hashes(vec![hash.clone()]))])
+            .await?
+            .iter()
+            .map(Object::from_row)
+            .collect())
+    }
+
     //ToDo: Docs
     pub async fn batch_create(objects: &[Object], client: &Client) -> Result<()> {
         // This is ugly but may solve our batch_create problems
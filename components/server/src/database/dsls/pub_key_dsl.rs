@@ -1,5 +1,5 @@
 use crate::database::crud::{CrudDb, PrimaryKey};
-use anyhow::Result;
+use anyhow::{bail, Result};
 use diesel_ulid::DieselUlid;
 use postgres_from_row::FromRow;
 use tokio_postgres::Client;
@@ -65,7 +65,17 @@ impl PubKey {
             .map(|e| PubKey::from_row(&e)))
     }
 
+    /// Queries the pub_keys table for all pubkeys registered for a specific proxy.
+    pub async fn get_by_proxy(proxy_id: &DieselUlid, client: &Client) -> Result<Vec<PubKey>> {
+        let query = "SELECT * FROM pub_keys WHERE proxy = $1";
+        let prepared = client.prepare(query).await?;
+        let rows = client.query(&prepared, &[proxy_id]).await?;
+        Ok(rows.iter().map(PubKey::from_row).collect::<Vec<_>>())
+    }
+
     /// As the primary key is a auto incrementing serial it is unnecessary to provide an id for inserts...
+    /// Fails if the pubkey is already registered for a different proxy (or the server),
+    /// as its serial would otherwise be shared between both.
     pub async fn create_or_get_without_id(
         proxy: Option<DieselUlid>,
         pubkey: &str,
@@ -81,9 +91,18 @@ impl PubKey {
         // Execute prepared statement
         let pubkey = match client.query_opt(&prepared, &[&proxy, &pubkey]).await? {
             Some(row) => PubKey::from_row(&row),
-            None => PubKey::get_by_key(pubkey, client)
-                .await?
-                .ok_or_else(|| anyhow::anyhow!("Broken."))?,
+            None => {
+                let existing = PubKey::get_by_key(pubkey, client)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Broken."))?;
+                if existing.proxy != proxy {
+                    bail!(
+                        "Pubkey serial {} is already claimed by another node",
+                        existing.id
+                    );
+                }
+                existing
+            }
         };
 
         // Return inserted/fetched pubkey
@@ -7,8 +7,8 @@ use diesel_ulid::DieselUlid;
 use postgres_from_row::FromRow;
 use postgres_types::Json;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt::Display};
-use tokio_postgres::Client;
+use std::{collections::HashMap, error::Error, fmt::Display};
+use tokio_postgres::{error::SqlState, Client};
 
 use super::{
     super::crud::{CrudDb, PrimaryKey},
@@ -26,6 +26,50 @@ pub struct User {
     pub active: bool,
 }
 
+/// Returned when a user is created or updated with an email that
+/// already belongs to another user
+#[derive(Debug)]
+pub struct EmailInUseError;
+impl Display for EmailInUseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Email already in use")
+    }
+}
+impl Error for EmailInUseError {}
+
+/// Returned when a user already has a token with the requested name
+#[derive(Debug)]
+pub struct TokenNameInUseError;
+impl Display for TokenNameInUseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Token name already in use")
+    }
+}
+impl Error for TokenNameInUseError {}
+
+/// Returned when an OIDC identity is added that is already mapped to another user
+#[derive(Debug)]
+pub struct OIDCMappingInUseError;
+impl Display for OIDCMappingInUseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Oidc ID already registered")
+    }
+}
+impl Error for OIDCMappingInUseError {}
+
+/// Maps violations of the unique email index and the OIDC mapping table
+/// to `EmailInUseError` and `OIDCMappingInUseError`
+fn map_unique_violation(err: tokio_postgres::Error) -> anyhow::Error {
+    match err.as_db_error() {
+        Some(db_err) if db_err.code() == &SqlState::UNIQUE_VIOLATION => match db_err.constraint() {
+            Some("users_email_idx") => anyhow!(EmailInUseError),
+            Some("user_oidc_mappings_pkey") => anyhow!(OIDCMappingInUseError),
+            _ => err.into(),
+        },
+        _ => err.into(),
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, FromRow, Debug, Eq, PartialEq, PartialOrd)]
 pub struct APIToken {
     pub pub_key: i32,
@@ -36,7 +80,7 @@ pub struct APIToken {
     pub user_rights: DbPermissionLevel,
 }
 
-#[derive(Serialize, Deserialize, Clone, FromRow, Debug, Eq, PartialEq, PartialOrd)]
+#[derive(Serialize, Deserialize, Clone, FromRow, Debug, Eq, PartialEq, PartialOrd, Hash)]
 pub struct OIDCMapping {
     pub external_id: String,
     pub oidc_name: String,
@@ -93,7 +137,8 @@ impl CrudDb for User {
                     &self.active,
                 ],
             )
-            .await?;
+            .await
+            .map_err(map_unique_violation)?;
         Ok(())
     }
 
@@ -159,11 +204,20 @@ impl User {
         let prepared = client.prepare(query).await?;
         let row = client
             .query_one(&prepared, &[&email.into(), user_id])
-            .await?;
+            .await
+            .map_err(map_unique_violation)?;
 
         Ok(User::from_row(&row))
     }
 
+    /// Fetches all users registered with the provided email address
+    pub async fn get_by_email(client: &Client, email: &str) -> Result<Vec<User>> {
+        let query = "SELECT * FROM users WHERE lower(email) = lower($1);";
+        let prepared = client.prepare(query).await?;
+        let rows = client.query(&prepared, &[&email]).await?;
+        Ok(rows.iter().map(User::from_row).collect::<Vec<_>>())
+    }
+
     //ToDo: Rust Doc
     pub async fn set_user_attributes(
         client: &Client,
@@ -365,6 +419,68 @@ impl User {
         Ok(User::from_row(&row))
     }
 
+    /// Appends the OIDC mapping to the users external ids.
+    /// Returns `None` if the user does not exist or already has this mapping.
+    pub async fn add_oidc_mapping(
+        client: &Client,
+        user_id: &DieselUlid,
+        mapping: &OIDCMapping,
+    ) -> Result<Option<User>> {
+        let query = "UPDATE users
+            SET attributes = jsonb_set(attributes, '{external_ids}', attributes->'external_ids' || $1::jsonb, true)
+            WHERE id = $2 AND NOT attributes->'external_ids' @> $1::jsonb
+            RETURNING *;";
+
+        let prepared = client.prepare(query).await?;
+        let mappings = Json(vec![mapping]);
+        Ok(client
+            .query_opt(&prepared, &[&mappings, user_id])
+            .await
+            .map_err(map_unique_violation)?
+            .map(|row| User::from_row(&row)))
+    }
+
+    /// Returns the user the OIDC identity is mapped to
+    pub async fn get_by_oidc_mapping(
+        client: &Client,
+        mapping: &OIDCMapping,
+    ) -> Result<Option<User>> {
+        let query = "SELECT u.* FROM users u
+            JOIN user_oidc_mappings m ON m.user_id = u.id
+            WHERE m.oidc_name = $1 AND m.external_id = $2;";
+
+        let prepared = client.prepare(query).await?;
+        Ok(client
+            .query_opt(&prepared, &[&mapping.oidc_name, &mapping.external_id])
+            .await?
+            .map(|row| User::from_row(&row)))
+    }
+
+    /// Removes all external ids of the provider from the user.
+    /// Returns `None` if the user does not exist or no other external id would remain.
+    pub async fn remove_oidc_provider(
+        client: &Client,
+        user_id: &DieselUlid,
+        provider_name: &str,
+    ) -> Result<Option<User>> {
+        let query = "UPDATE users
+            SET attributes = jsonb_set(attributes, '{external_ids}', (
+                SELECT jsonb_agg(e) FROM jsonb_array_elements(attributes->'external_ids') e
+                WHERE e->>'oidc_name' <> $1
+            ))
+            WHERE id = $2 AND EXISTS (
+                SELECT 1 FROM jsonb_array_elements(attributes->'external_ids') e
+                WHERE e->>'oidc_name' <> $1
+            )
+            RETURNING *;";
+
+        let prepared = client.prepare(query).await?;
+        Ok(client
+            .query_opt(&prepared, &[&provider_name, user_id])
+            .await?
+            .map(|row| User::from_row(&row)))
+    }
+
     //ToDo: Rust Doc
     pub async fn add_trusted_endpoint(
         client: &Client,
@@ -466,6 +582,19 @@ impl User {
 
         Ok(User::from_row(&row))
     }
+
+    /// Token names are unique per user, unnamed tokens are not counted
+    pub fn count_tokens_named(&self, name: &str) -> usize {
+        if name.is_empty() {
+            return 0;
+        }
+        self.attributes
+            .0
+            .tokens
+            .iter()
+            .filter(|token| token.value().name == name)
+            .count()
+    }
 }
 
 impl Display for UserAttributes {
@@ -218,6 +218,42 @@ pub enum ReplicationStatus {
     Error,
 }
 
+impl ReplicationStatus {
+    /// Replication only moves forward (Waiting -> Running -> Finished) or fails,
+    /// repeating the current status is accepted as a no-op
+    pub fn can_transition_to(&self, next: &ReplicationStatus) -> bool {
+        matches!(
+            (self, next),
+            (_, ReplicationStatus::Error)
+                | (ReplicationStatus::Waiting, ReplicationStatus::Waiting)
+                | (ReplicationStatus::Waiting, ReplicationStatus::Running)
+                | (ReplicationStatus::Running, ReplicationStatus::Running)
+                | (ReplicationStatus::Running, ReplicationStatus::Finished)
+                | (ReplicationStatus::Finished, ReplicationStatus::Finished)
+        )
+    }
+
+    /// Rolls per-endpoint statuses up into an overall status:
+    /// Error if any failed, Finished if all finished, Running if any progress
+    /// was made and Waiting otherwise. Returns None without any status.
+    pub fn aggregate<'a>(
+        statuses: impl IntoIterator<Item = &'a ReplicationStatus>,
+    ) -> Option<ReplicationStatus> {
+        let mut overall = None;
+        for status in statuses {
+            overall = Some(match (overall, status) {
+                (_, ReplicationStatus::Error) | (Some(ReplicationStatus::Error), _) => {
+                    ReplicationStatus::Error
+                }
+                (None, status) => *status,
+                (Some(current), status) if current == *status => current,
+                (Some(_), _) => ReplicationStatus::Running,
+            });
+        }
+        overall
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
 pub enum ReplicationType {
     FullSync,
@@ -124,6 +124,44 @@ CREATE TABLE IF NOT EXISTS users (
     attributes JSONB NOT NULL,
     active BOOL NOT NULL DEFAULT FALSE
 );
+-- Emails are unique per user regardless of case, unset emails are ignored
+CREATE UNIQUE INDEX IF NOT EXISTS users_email_idx ON users (lower(email)) WHERE email <> '';
+
+-- OIDC identities of users, kept in sync with users.attributes->'external_ids'
+-- An identity can only be mapped to a single user
+CREATE TABLE IF NOT EXISTS user_oidc_mappings (
+    oidc_name TEXT NOT NULL,
+    external_id TEXT NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (oidc_name, external_id)
+);
+CREATE OR REPLACE FUNCTION sync_user_oidc_mappings() RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM user_oidc_mappings WHERE user_id = NEW.id;
+    INSERT INTO user_oidc_mappings (oidc_name, external_id, user_id)
+        SELECT e->>'oidc_name', e->>'external_id', NEW.id
+        FROM jsonb_array_elements(NEW.attributes->'external_ids') e;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+DO $$
+    BEGIN
+        IF NOT EXISTS (SELECT 1 FROM pg_trigger WHERE tgname = 'users_oidc_mappings_insert') THEN
+            CREATE TRIGGER users_oidc_mappings_insert AFTER INSERT ON users
+                FOR EACH ROW EXECUTE FUNCTION sync_user_oidc_mappings();
+        END IF;
+        IF NOT EXISTS (SELECT 1 FROM pg_trigger WHERE tgname = 'users_oidc_mappings_update') THEN
+            CREATE TRIGGER users_oidc_mappings_update AFTER UPDATE ON users
+                FOR EACH ROW WHEN (OLD.attributes->'external_ids' IS DISTINCT FROM NEW.attributes->'external_ids')
+                EXECUTE FUNCTION sync_user_oidc_mappings();
+        END IF;
+    END
+$$;
+-- Backfill users created before the mapping table existed
+INSERT INTO user_oidc_mappings (oidc_name, external_id, user_id)
+    SELECT e->>'oidc_name', e->>'external_id', u.id
+    FROM users u, jsonb_array_elements(u.attributes->'external_ids') e
+    ON CONFLICT DO NOTHING;
 
 CREATE TABLE IF NOT EXISTS identity_providers (
     issuer_name VARCHAR(255) PRIMARY KEY,
@@ -166,6 +204,7 @@ CREATE TABLE IF NOT EXISTS objects (
     UNIQUE(id, object_type)
 );
 CREATE INDEX IF NOT EXISTS objects_pk_idx ON objects (id);
+CREATE INDEX IF NOT EXISTS objects_hashes_idx ON objects USING GIN (hashes);
 
 -- Table with endpoints
 CREATE TABLE IF NOT EXISTS endpoints (
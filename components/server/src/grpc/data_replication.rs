@@ -156,22 +156,23 @@ impl DataReplicationService for DataReplicationServiceImpl {
         let token = tonic_auth!(get_token_from_md(&metadata), "Token authentication error");
 
         let ctx = Context::proxy();
-        let PermissionCheck { is_proxy, .. } = tonic_auth!(
+        let PermissionCheck { proxy_id, .. } = tonic_auth!(
             self.authorizer
                 .check_permissions_verbose(&token, vec![ctx])
                 .await,
             "Unauthorized"
         );
 
-        // TODO: Should user be able to update replication status?
-        if is_proxy {
-            tonic_internal!(
-                self.database_handler
-                    .update_replication_status(request)
-                    .await,
-                "Internal replication error"
-            );
+        // Only the proxy holding the data location may update its status
+        if proxy_id.map(|id| id.to_string()) != Some(request.endpoint_id.clone()) {
+            return Err(tonic::Status::unauthenticated("Unauthorized"));
         }
+        tonic_internal!(
+            self.database_handler
+                .update_replication_status(request)
+                .await,
+            "Internal replication error"
+        );
         return_with_log!(UpdateReplicationStatusResponse {});
     }
     async fn delete_replication(
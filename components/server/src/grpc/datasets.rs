@@ -22,7 +22,7 @@ use crate::auth::structs::Context;
 use crate::caching::cache::Cache;
 use crate::caching::structs::ObjectWrapper;
 use crate::database::dsls::object_dsl::ObjectWithRelations;
-use crate::database::enums::DbPermissionLevel;
+use crate::database::enums::{DataClass, DbPermissionLevel};
 use crate::middlelayer::create_request_types::CreateRequest;
 use crate::middlelayer::db_handler::DatabaseHandler;
 use crate::middlelayer::delete_request_types::DeleteRequest;
@@ -78,7 +78,7 @@ impl DatasetService for DatasetServiceImpl {
             .attributes
             .0
             .service_account;
-        if is_service_account && (request.get_data_class() != 4) {
+        if is_service_account && (request.get_data_class() != DataClass::WORKSPACE as i32) {
             return Err(tonic::Status::invalid_argument(
                 "Workspaces have to be claimed for dataclass changes",
             ));
@@ -345,8 +345,13 @@ impl DatasetService for DatasetServiceImpl {
             "Unauthorized"
         );
 
+        // Removing static labels requires admin permissions
+        let is_admin = self.authorizer.is_resource_admin(&token, dataset_id).await;
+
         let mut dataset = tonic_internal!(
-            self.database_handler.update_keyvals(request).await,
+            self.database_handler
+                .update_keyvals(request, is_admin)
+                .await,
             "Internal database error."
         );
         self.cache
@@ -3,13 +3,14 @@ use crate::auth::structs::Context;
 use crate::caching::cache::Cache;
 use crate::middlelayer::db_handler::DatabaseHandler;
 use crate::utils::grpc_utils::get_token_from_md;
+use aruna_rust_api::api::storage::models::v2::ComponentStatus as ProtoComponentStatus;
 use aruna_rust_api::api::storage::services::v2::storage_status_service_server::StorageStatusService;
 use aruna_rust_api::api::storage::services::v2::{
-    GetAnnouncementRequest, GetAnnouncementResponse, GetAnnouncementsByTypeRequest,
-    GetAnnouncementsByTypeResponse, GetAnnouncementsRequest, GetAnnouncementsResponse,
-    GetPubkeysRequest, GetPubkeysResponse, GetStorageStatusRequest, GetStorageStatusResponse,
-    GetStorageVersionRequest, GetStorageVersionResponse, SetAnnouncementsRequest,
-    SetAnnouncementsResponse,
+    ComponentStatus, ComponentVersion, GetAnnouncementRequest, GetAnnouncementResponse,
+    GetAnnouncementsByTypeRequest, GetAnnouncementsByTypeResponse, GetAnnouncementsRequest,
+    GetAnnouncementsResponse, GetPubkeysRequest, GetPubkeysResponse, GetStorageStatusRequest,
+    GetStorageStatusResponse, GetStorageVersionRequest, GetStorageVersionResponse, LocationStatus,
+    LocationVersion, SemanticVersion, SetAnnouncementsRequest, SetAnnouncementsResponse,
 };
 use diesel_ulid::DieselUlid;
 use std::str::FromStr;
@@ -18,6 +19,10 @@ use tonic::Response;
 
 crate::impl_grpc_server!(StorageStatusServiceImpl);
 
+pub const SERVER_LOCATION: &str = "aruna";
+pub const SERVER_COMPONENT: &str = "server";
+pub const DATAPROXY_COMPONENT: &str = "dataproxy";
+
 #[tonic::async_trait]
 impl StorageStatusService for StorageStatusServiceImpl {
     /// GetStorageVersion
@@ -28,9 +33,36 @@ impl StorageStatusService for StorageStatusServiceImpl {
     /// String representation and https://semver.org/
     async fn get_storage_version(
         &self,
-        _request: tonic::Request<GetStorageVersionRequest>,
+        request: tonic::Request<GetStorageVersionRequest>,
     ) -> Result<Response<GetStorageVersionResponse>, tonic::Status> {
-        Err(tonic::Status::unimplemented("Nothing to see here!"))
+        log_received!(&request);
+
+        let response = GetStorageVersionResponse {
+            location_version: vec![LocationVersion {
+                location: SERVER_LOCATION.to_string(),
+                version: vec![ComponentVersion {
+                    name: SERVER_COMPONENT.to_string(),
+                    version: Some(SemanticVersion {
+                        version_string: env!("CARGO_PKG_VERSION").to_string(),
+                        major: tonic_internal!(
+                            env!("CARGO_PKG_VERSION_MAJOR").parse::<i32>(),
+                            "Invalid server version"
+                        ),
+                        minor: tonic_internal!(
+                            env!("CARGO_PKG_VERSION_MINOR").parse::<i32>(),
+                            "Invalid server version"
+                        ),
+                        patch: tonic_internal!(
+                            env!("CARGO_PKG_VERSION_PATCH").parse::<i32>(),
+                            "Invalid server version"
+                        ),
+                        labels: env!("CARGO_PKG_VERSION_PRE").to_string(),
+                    }),
+                }],
+            }],
+        };
+
+        return_with_log!(response);
     }
     /// GetStorageStatus
     ///
@@ -39,9 +71,35 @@ impl StorageStatusService for StorageStatusServiceImpl {
     /// A request to get the current status of the storage components by location(s)
     async fn get_storage_status(
         &self,
-        _request: tonic::Request<GetStorageStatusRequest>,
+        request: tonic::Request<GetStorageStatusRequest>,
     ) -> Result<Response<GetStorageStatusResponse>, tonic::Status> {
-        Err(tonic::Status::unimplemented("Nothing to see here!"))
+        log_received!(&request);
+
+        // The server answering this request is available by definition
+        let mut location_status = vec![LocationStatus {
+            location: SERVER_LOCATION.to_string(),
+            component_status: vec![ComponentStatus {
+                name: SERVER_COMPONENT.to_string(),
+                status: ProtoComponentStatus::Available as i32,
+            }],
+        }];
+
+        // Dataproxies report their last known status
+        let endpoints = tonic_internal!(
+            self.database_handler.get_endpoints().await,
+            "Internal get endpoints error"
+        );
+        location_status.extend(endpoints.into_iter().map(|endpoint| LocationStatus {
+            location: endpoint.name,
+            component_status: vec![ComponentStatus {
+                name: DATAPROXY_COMPONENT.to_string(),
+                status: endpoint.status.into(),
+            }],
+        }));
+
+        let response = GetStorageStatusResponse { location_status };
+
+        return_with_log!(response);
     }
 
     async fn get_pubkeys(
@@ -486,12 +486,20 @@ impl EventNotificationService for NotificationServiceImpl {
         );
 
         // Acknowledge provided messages
-        if let Err(err) = &self
+        match self
             .natsio_handler
             .acknowledge_from_reply(inner_request.replies)
             .await
         {
-            return Err(Status::aborted(err.to_string()));
+            Ok(dead_letters) => {
+                if !dead_letters.is_empty() {
+                    log::warn!(
+                        "{} replies could not be validated and were dead-lettered",
+                        dead_letters.len()
+                    )
+                }
+            }
+            Err(err) => return Err(Status::aborted(err.to_string())),
         }
 
         // Create and return gRPC response
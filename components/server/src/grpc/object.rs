@@ -21,7 +21,7 @@ use crate::auth::structs::Context;
 use crate::caching::cache::Cache;
 use crate::caching::structs::ObjectWrapper;
 use crate::database::dsls::object_dsl::ObjectWithRelations;
-use crate::database::enums::DbPermissionLevel;
+use crate::database::enums::{DataClass, DbPermissionLevel};
 use crate::middlelayer::clone_request_types::CloneObject;
 use crate::middlelayer::create_request_types::CreateRequest;
 use crate::middlelayer::db_handler::DatabaseHandler;
@@ -34,9 +34,14 @@ use crate::middlelayer::update_request_types::{
 use crate::search::meilisearch_client::{MeilisearchClient, ObjectDocument};
 use crate::utils::grpc_utils::get_token_from_md;
 use crate::utils::grpc_utils::{get_id_and_ctx, IntoGenericInner};
+use crate::utils::rate_limiter::RateLimiter;
 use crate::utils::search_utils;
 
-crate::impl_grpc_server!(ObjectServiceImpl, search_client: Arc<MeilisearchClient>);
+crate::impl_grpc_server!(
+    ObjectServiceImpl,
+    search_client: Arc<MeilisearchClient>,
+    url_rate_limiter: Option<Arc<RateLimiter>>
+);
 
 #[tonic::async_trait]
 impl ObjectService for ObjectServiceImpl {
@@ -76,7 +81,7 @@ impl ObjectService for ObjectServiceImpl {
             .attributes
             .0
             .service_account;
-        if is_service_account && (request.get_data_class() != 4) {
+        if is_service_account && (request.get_data_class() != DataClass::WORKSPACE as i32) {
             return Err(Status::invalid_argument(
                 "Workspaces have to be claimed for dataclass changes",
             ));
@@ -138,6 +143,12 @@ impl ObjectService for ObjectServiceImpl {
             "Unauthorized"
         );
 
+        if let Some(limiter) = &self.url_rate_limiter {
+            limiter
+                .check(&token.unwrap_or(user_id))
+                .map_err(|e| Status::resource_exhausted(e.to_string()))?;
+        }
+
         let (url, upload_id) = tonic_internal!(
             self.database_handler
                 .get_presigend_upload(
@@ -183,6 +194,22 @@ impl ObjectService for ObjectServiceImpl {
             "Unauthorized"
         );
 
+        if let Some(limiter) = &self.url_rate_limiter {
+            limiter
+                .check(&token.unwrap_or(user_id))
+                .map_err(|e| Status::resource_exhausted(e.to_string()))?;
+        }
+
+        let object = self
+            .cache
+            .get_object(&object_id)
+            .ok_or_else(|| Status::not_found("Object not found"))?;
+        if !object.object.is_downloadable() {
+            return Err(Status::failed_precondition(
+                "Object data is not available at any endpoint",
+            ));
+        }
+
         let signed_url = tonic_internal!(
             self.database_handler
                 .get_presigned_download(
@@ -324,9 +351,12 @@ impl ObjectService for ObjectServiceImpl {
             .0
             .service_account;
 
+        // Removing static labels requires admin permissions
+        let is_admin = self.authorizer.is_resource_admin(&token, object_id).await;
+
         let (object, new_revision) = tonic_internal!(
             self.database_handler
-                .update_grpc_object(inner, user_id, is_service_account)
+                .update_grpc_object(inner, user_id, is_service_account, is_admin)
                 .await,
             "Internal database error."
         );
@@ -329,8 +329,13 @@ impl ProjectService for ProjectServiceImpl {
             "Unauthorized"
         );
 
+        // Removing static labels requires admin permissions
+        let is_admin = self.authorizer.is_resource_admin(&token, project_id).await;
+
         let mut project = tonic_internal!(
-            self.database_handler.update_keyvals(request).await,
+            self.database_handler
+                .update_keyvals(request, is_admin)
+                .await,
             "Internal database error."
         );
         self.cache
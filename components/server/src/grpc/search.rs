@@ -1,5 +1,5 @@
 use crate::caching::cache::Cache;
-use crate::database::dsls::object_dsl::{KeyValues, ObjectWithRelations};
+use crate::database::dsls::object_dsl::{Hashes, KeyValues, ObjectWithRelations};
 use crate::database::enums::{DataClass, ObjectMapping};
 use crate::{auth::permission_handler::PermissionHandler, database::enums::DbPermissionLevel};
 use aruna_rust_api::api::storage::models::v2::generic_resource::Resource;
@@ -51,9 +51,9 @@ impl SearchService for SearchServiceImpl {
         // and this search function is a PUBLIC endpoint ON PURPOSE
         // to make everything FINDABLE
 
-        // Check if: 0 < limit <= 100
-        if (inner_request.limit < 1) || (inner_request.limit > 100) {
-            return Err(Status::invalid_argument("Limit must be between 1 and 100"));
+        // Check if: 0 <= limit <= 100; a limit of 0 only returns the estimated total
+        if (inner_request.limit < 0) || (inner_request.limit > 100) {
+            return Err(Status::invalid_argument("Limit must be between 0 and 100"));
         }
 
         // Search meilisearch index
@@ -177,35 +177,8 @@ impl SearchService for SearchServiceImpl {
                 .get_object_with_stats(&resource_ulid)
                 .ok_or_else(|| Status::not_found("Object not found"))?;
 
-            // Check if object metadata is publicly available
-            let bindings = match object_plus.object.data_class {
-                DataClass::PUBLIC => self
-                    .cache
-                    .get_rule_bindings(&resource_ulid)
-                    .unwrap_or_default(),
-                DataClass::PRIVATE => {
-                    // SPECIFIC private operations OTHER THAN strip labels
-                    // Remove created by
-                    object_plus.object.created_by = DieselUlid::default();
-                    // Endpoint redaction
-                    object_plus.object.endpoints = Json(DashMap::default());
-                    Arc::new(vec![])
-                }
-                _ => return Err(Status::invalid_argument("Resource is not public")),
-            };
-
-            // Strip infos
-            let stripped_labels = object_plus
-                .object
-                .key_values
-                .0
-                 .0
-                .into_iter()
-                .filter(|kv| !kv.key.contains("app.aruna-storage"))
-                .filter(|kv| !kv.key.contains("private"))
-                .collect::<Vec<_>>();
-
-            object_plus.object.key_values = Json(KeyValues(stripped_labels));
+            let bindings = redact_public_view(&self.cache, &mut object_plus)
+                .ok_or_else(|| Status::invalid_argument("Resource is not public"))?;
             (object_plus, PermissionLevel::None, bindings)
         };
         self.cache.add_stats_to_object(&mut object_plus);
@@ -328,32 +301,8 @@ impl SearchService for SearchServiceImpl {
                     .get_object_with_stats(&id)
                     .ok_or_else(|| Status::not_found("Object not found"))?;
 
-                // Check if object metadata is publicly available
-                let bindings = match object_plus.object.data_class {
-                    DataClass::PUBLIC => self.cache.get_rule_bindings(&id).unwrap_or_default(),
-                    DataClass::PRIVATE => {
-                        // SPECIFIC private operations OTHER THAN strip labels
-                        // Remove created by
-                        object_plus.object.created_by = DieselUlid::default();
-                        // Endpoint redaction
-                        object_plus.object.endpoints = Json(DashMap::default());
-                        Arc::new(Vec::new())
-                    }
-                    _ => return Err(Status::invalid_argument("Resource is not public")),
-                };
-
-                // Strip infos
-                let stripped_labels = object_plus
-                    .object
-                    .key_values
-                    .0
-                     .0
-                    .into_iter()
-                    .filter(|kv| !kv.key.contains("app.aruna-storage"))
-                    .filter(|kv| !kv.key.contains("private"))
-                    .collect::<Vec<_>>();
-
-                object_plus.object.key_values = Json(KeyValues(stripped_labels));
+                let bindings = redact_public_view(&self.cache, &mut object_plus)
+                    .ok_or_else(|| Status::invalid_argument("Resource is not public"))?;
                 objects.push((object_plus, PermissionLevel::None, bindings));
             }
             objects
@@ -422,3 +371,39 @@ impl SearchService for SearchServiceImpl {
         return_with_log!(response);
     }
 }
+
+/// Redacts a resource for requests without READ permission and returns the
+/// rule bindings which are visible to such requests, or None if the resource
+/// is not public at all.
+fn redact_public_view(
+    cache: &Cache,
+    object_plus: &mut ObjectWithRelations,
+) -> Option<Arc<Vec<RuleBinding>>> {
+    // Check if object metadata is publicly available
+    let bindings = match object_plus.object.data_class {
+        DataClass::PUBLIC => cache
+            .get_rule_bindings(&object_plus.object.id)
+            .unwrap_or_default(),
+        DataClass::PRIVATE => {
+            // SPECIFIC private operations OTHER THAN strip labels
+            // Remove created by
+            object_plus.object.created_by = DieselUlid::default();
+            // Endpoint redaction
+            object_plus.object.endpoints = Json(DashMap::default());
+            // Hashes belong to the data, which is not public
+            object_plus.object.hashes = Json(Hashes(vec![]));
+            Arc::new(vec![])
+        }
+        _ => return None,
+    };
+
+    // Strip infos
+    let stripped_labels = std::mem::take(&mut object_plus.object.key_values.0 .0)
+        .into_iter()
+        .filter(|kv| !kv.key.contains("app.aruna-storage"))
+        .filter(|kv| !kv.key.contains("private"))
+        .collect::<Vec<_>>();
+
+    object_plus.object.key_values = Json(KeyValues(stripped_labels));
+    Some(bindings)
+}
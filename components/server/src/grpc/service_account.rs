@@ -3,7 +3,7 @@ use crate::auth::structs::{Context, ContextVariant};
 use crate::auth::token_handler::{Action, Intent, ProcessedToken};
 use crate::caching::cache::Cache;
 use crate::database::crud::CrudDb;
-use crate::database::dsls::user_dsl::User;
+use crate::database::dsls::user_dsl::{TokenNameInUseError, User};
 use crate::database::enums::{DbPermissionLevel, ObjectType};
 use crate::middlelayer::db_handler::DatabaseHandler;
 use crate::middlelayer::service_account_request_types::{
@@ -40,6 +40,7 @@ use aruna_rust_api::api::storage::services::v2::{
     RemoveTrustedEndpointsSvcAccountResponse,
 };
 use diesel_ulid::DieselUlid;
+use log::error;
 use std::sync::Arc;
 use tonic::{Request, Response, Result, Status};
 
@@ -112,12 +113,20 @@ impl ServiceAccountService for ServiceAccountServiceImpl {
                 .await,
             "Unauthorized"
         );
-        let (token, token_secret) = tonic_internal!(
-            self.database_handler
-                .create_service_account_token(self.authorizer.clone(), request)
-                .await,
-            "Internal create service account error"
-        );
+        let (token, token_secret) = match self
+            .database_handler
+            .create_service_account_token(self.authorizer.clone(), request)
+            .await
+        {
+            Ok(token) => token,
+            Err(err) => {
+                error!("{:?}", err);
+                return match err.downcast_ref::<TokenNameInUseError>() {
+                    Some(_) => Err(Status::already_exists("Token name already in use")),
+                    None => Err(Status::internal("Internal create service account error")),
+                };
+            }
+        };
         let response = CreateServiceAccountTokenResponse {
             token,
             token_secret,
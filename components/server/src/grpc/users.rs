@@ -2,6 +2,7 @@ use crate::auth::permission_handler::{PermissionCheck, PermissionHandler};
 use crate::auth::structs::Context;
 use crate::auth::token_handler::{Action, Intent, ProcessedToken, TokenHandler};
 use crate::caching::cache::Cache;
+use crate::database::dsls::user_dsl::{EmailInUseError, TokenNameInUseError};
 use crate::database::enums::DbPermissionLevel;
 use crate::middlelayer::db_handler::DatabaseHandler;
 use crate::middlelayer::token_request_types::{CreateToken, DeleteToken, GetToken};
@@ -65,17 +66,24 @@ impl UserService for UserServiceImpl {
         if request.get_email().is_empty() {
             return Err(Status::invalid_argument("Email is mandatory"));
         }
-
         let external_id = tonic_auth!(
             self.authorizer.check_unregistered_oidc(&token).await,
             "Unauthorized"
         );
-        let user = tonic_internal!(
-            self.database_handler
-                .register_user(request, external_id)
-                .await,
-            "Internal register user error"
-        );
+        let user = match self
+            .database_handler
+            .register_user(request, external_id)
+            .await
+        {
+            Ok(user) => user,
+            Err(err) => {
+                error!("{:?}", err);
+                return match err.downcast_ref::<EmailInUseError>() {
+                    Some(_) => Err(Status::already_exists("Email already in use")),
+                    None => Err(Status::internal("Internal register user error")),
+                };
+            }
+        };
 
         // Try to send mail to recently registered users email address
         if let Some(mailclient) = self.mailclient.as_ref() {
@@ -184,23 +192,31 @@ the Aruna team", user.display_name),
 
         // Create token in database
         let middlelayer_request = CreateToken(inner_request);
-        let (token_ulid, token) = tonic_internal!(
-            self.database_handler
-                .create_token(
-                    &user_id,
-                    self.token_handler.get_current_pubkey_serial() as i32,
-                    middlelayer_request.clone(),
-                )
-                .await,
-            "Token creation failed"
-        );
+        let (token_ulid, token) = match self
+            .database_handler
+            .create_token(
+                &user_id,
+                self.token_handler.get_current_pubkey_serial() as i32,
+                middlelayer_request.clone(),
+            )
+            .await
+        {
+            Ok(token) => token,
+            Err(err) => {
+                error!("{:?}", err);
+                return match err.downcast_ref::<TokenNameInUseError>() {
+                    Some(_) => Err(Status::already_exists("Token name already in use")),
+                    None => Err(Status::internal("Token creation failed")),
+                };
+            }
+        };
 
         // Sign token
         let token_secret = tonic_internal!(
             self.token_handler.sign_user_token(
                 &user_id,
                 &token_ulid,
-                middlelayer_request.0.expires_at,
+                Some(token.expires_at.into()),
             ),
             "Token signing failed"
         );
@@ -438,11 +454,16 @@ the Aruna team", user.display_name),
             self.authorizer.check_permissions(&token, vec![ctx]).await,
             "Unauthorized"
         );
-
-        let user = tonic_internal!(
-            self.database_handler.update_email(request, user_id).await,
-            "Internal deactivate user error"
-        );
+        let user = match self.database_handler.update_email(request, user_id).await {
+            Ok(user) => user,
+            Err(err) => {
+                error!("{:?}", err);
+                return match err.downcast_ref::<EmailInUseError>() {
+                    Some(_) => Err(Status::already_exists("Email already in use")),
+                    None => Err(Status::internal("Internal update user email error")),
+                };
+            }
+        };
 
         let response = UpdateUserEmailResponse {
             user: Some(user.into()),
@@ -1,5 +1,6 @@
 use crate::auth::structs::Context;
 use crate::caching::cache::Cache;
+use crate::database::dsls::user_dsl::TokenNameInUseError;
 use crate::middlelayer::db_handler::DatabaseHandler;
 use crate::middlelayer::workspace_request_types::{CreateTemplate, CreateWorkspace};
 use crate::{auth::permission_handler::PermissionHandler, utils::grpc_utils::get_token_from_md};
@@ -14,9 +15,10 @@ use aruna_rust_api::api::storage::services::v2::{
     ListOwnedWorkspaceTemplatesResponse,
 };
 
+use log::error;
 use std::str::FromStr;
 use std::sync::Arc;
-use tonic::{Request, Response, Result};
+use tonic::{Request, Response, Result, Status};
 crate::impl_grpc_server!(WorkspaceServiceImpl, default_endpoint: String);
 
 #[tonic::async_trait]
@@ -69,12 +71,20 @@ impl WorkspaceService for WorkspaceServiceImpl {
 
         let endpoint = self.default_endpoint.clone();
         // Create template
-        let (workspace_id, access_key, secret_key, token) = tonic_invalid!(
-            self.database_handler
-                .create_workspace(self.authorizer.clone(), request, endpoint)
-                .await,
-            "Invalid request"
-        );
+        let (workspace_id, access_key, secret_key, token) = match self
+            .database_handler
+            .create_workspace(self.authorizer.clone(), request, endpoint)
+            .await
+        {
+            Ok(workspace) => workspace,
+            Err(err) => {
+                error!("{:?}", err);
+                return match err.downcast_ref::<TokenNameInUseError>() {
+                    Some(_) => Err(Status::already_exists("Token name already in use")),
+                    None => Err(Status::invalid_argument("Invalid request")),
+                };
+            }
+        };
 
         let response = CreateWorkspaceResponse {
             workspace_id: workspace_id.to_string(),
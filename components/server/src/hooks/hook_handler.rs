@@ -270,7 +270,7 @@ impl HookHandler {
                         remove_key_values: Vec::new(),
                     },
                 );
-                self.database_handler.update_keyvals(request).await?;
+                self.database_handler.update_keyvals(request, false).await?;
             }
             ObjectType::COLLECTION => {
                 let request = crate::middlelayer::update_request_types::KeyValueUpdate::Collection(
@@ -284,7 +284,7 @@ impl HookHandler {
                         remove_key_values: Vec::new(),
                     },
                 );
-                self.database_handler.update_keyvals(request).await?;
+                self.database_handler.update_keyvals(request, false).await?;
             }
             ObjectType::DATASET => {
                 let request = crate::middlelayer::update_request_types::KeyValueUpdate::Collection(
@@ -298,7 +298,7 @@ impl HookHandler {
                         remove_key_values: Vec::new(),
                     },
                 );
-                self.database_handler.update_keyvals(request).await?;
+                self.database_handler.update_keyvals(request, false).await?;
             }
             ObjectType::OBJECT => {
                 let request = UpdateObjectRequest {
@@ -327,7 +327,7 @@ impl HookHandler {
                     .0
                     .service_account;
                 self.database_handler
-                    .update_grpc_object(request, user_id, is_service_account)
+                    .update_grpc_object(request, user_id, is_service_account, false)
                     .await?;
             }
         }
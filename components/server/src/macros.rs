@@ -111,8 +111,7 @@ macro_rules! tonic_auth {
     ($result:expr, $message:expr) => {
         $result.map_err(|e| {
             log::error!("{}", e);
-            let msg = format!("{} : {}", $message, e);
-            tonic::Status::unauthenticated(msg)
+            $crate::utils::grpc_utils::IntoAuthStatus::into_auth_status(e, $message)
         })?
     };
 }
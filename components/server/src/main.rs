@@ -24,7 +24,10 @@ use aruna_server::{
     database::{
         self,
         crud::CrudDb,
-        dsls::{endpoint_dsl::Endpoint, stats_dsl::start_refresh_loop},
+        dsls::{
+            endpoint_dsl::{start_health_loop, Endpoint},
+            stats_dsl::start_refresh_loop,
+        },
     },
     grpc::{
         authorization::AuthorizationServiceImpl, collections::CollectionServiceImpl,
@@ -36,9 +39,11 @@ use aruna_server::{
     },
     hooks,
     middlelayer::db_handler::DatabaseHandler,
-    notification::natsio_handler::NatsIoHandler,
+    notification::natsio_handler::{
+        NatsIoHandler, DEFAULT_FETCH_EXPIRY_MS, DEFAULT_FETCH_MAX_BYTES,
+    },
     search::meilisearch_client::{MeilisearchClient, MeilisearchIndexes},
-    utils::{mailclient::MailClient, search_utils},
+    utils::{mailclient::MailClient, rate_limiter::RateLimiter, search_utils},
 };
 use diesel_ulid::DieselUlid;
 use log::{error, info, warn};
@@ -76,26 +81,19 @@ pub async fn main() -> Result<()> {
     // Init cache
     let cache_arc = Cache::new();
 
-    // Init TokenHandler
-    let token_handler = TokenHandler::new(
-        cache_arc.clone(),
-        db_arc.clone(),
-        dotenvy::var("ENCODING_KEY")?,
-        dotenvy::var("DECODING_KEY")?,
-    )
-    .await?;
-    let token_handler_arc = Arc::new(token_handler);
-    cache_arc.sync_cache(db_arc.clone()).await?;
-
-    // Init PermissionHandler
-    let authorizer = PermissionHandler::new(cache_arc.clone(), token_handler_arc.clone());
-    let auth_arc = Arc::new(authorizer);
-
     // Init NatsIoHandler
     let client = async_nats::connect(dotenvy::var("NATS_HOST")?).await?;
     let natsio_handler = NatsIoHandler::new(client, dotenvy::var("REPLY_SECRET")?, None)
         .await
-        .map_err(|_| anyhow::anyhow!("NatsIoHandler init failed"))?;
+        .map_err(|_| anyhow::anyhow!("NatsIoHandler init failed"))?
+        .with_fetch_limits(
+            dotenvy::var("NATS_FETCH_MAX_BYTES")
+                .map(|var| var.parse::<usize>().unwrap_or(DEFAULT_FETCH_MAX_BYTES))
+                .unwrap_or(DEFAULT_FETCH_MAX_BYTES),
+            dotenvy::var("NATS_FETCH_EXPIRY")
+                .map(|var| var.parse::<u64>().unwrap_or(DEFAULT_FETCH_EXPIRY_MS))
+                .unwrap_or(DEFAULT_FETCH_EXPIRY_MS),
+        );
     let natsio_arc = Arc::new(natsio_handler);
 
     // Create channel for HookHandler
@@ -110,6 +108,29 @@ pub async fn main() -> Result<()> {
     };
     let db_handler_arc = Arc::new(database_handler);
 
+    // Init TokenHandler
+    let token_handler = TokenHandler::new(
+        cache_arc.clone(),
+        db_arc.clone(),
+        dotenvy::var("ENCODING_KEY")?,
+        dotenvy::var("DECODING_KEY")?,
+    )
+    .await?;
+    let token_handler = if dotenvy::var("AUTO_REGISTER_OIDC")
+        .map(|var| var.parse::<bool>().unwrap_or(false))
+        .unwrap_or(false)
+    {
+        token_handler.with_oidc_auto_register(db_handler_arc.clone())
+    } else {
+        token_handler
+    };
+    let token_handler_arc = Arc::new(token_handler);
+    cache_arc.sync_cache(db_arc.clone()).await?;
+
+    // Init PermissionHandler
+    let authorizer = PermissionHandler::new(cache_arc.clone(), token_handler_arc.clone());
+    let auth_arc = Arc::new(authorizer);
+
     // Init HookHandler
     let auth_clone = auth_arc.clone();
     let db_clone = db_handler_arc.clone();
@@ -141,11 +162,34 @@ pub async fn main() -> Result<()> {
             warn!("Search index creation failed: {}", err)
         };
 
+        // Configure stop words and synonyms
+        if let Ok(stop_words) = dotenvy::var("SEARCH_STOP_WORDS") {
+            if let Err(err) = search_clone
+                .set_stop_words(
+                    &MeilisearchIndexes::OBJECT.to_string(),
+                    &search_utils::parse_stop_words(&stop_words),
+                )
+                .await
+            {
+                warn!("Search index stop words update failed: {}", err)
+            }
+        }
+        if let Ok(synonyms) = dotenvy::var("SEARCH_SYNONYMS") {
+            if let Err(err) = search_clone
+                .set_synonyms(
+                    &MeilisearchIndexes::OBJECT.to_string(),
+                    &search_utils::parse_synonym_groups(&synonyms),
+                )
+                .await
+            {
+                warn!("Search index synonyms update failed: {}", err)
+            }
+        }
+
         // Full sync search index with database content
-        if let Err(err) =
-            search_utils::full_sync_search_index(db_clone, cache_clone, search_clone).await
-        {
-            warn!("Search index full sync failed: {}", err)
+        match search_utils::full_sync_search_index(db_clone, cache_clone, search_clone).await {
+            Ok(count) => info!("Search index full sync finished: {} documents", count),
+            Err(err) => warn!("Search index full sync failed: {}", err),
         };
 
         Ok::<(), anyhow::Error>(())
@@ -181,6 +225,23 @@ pub async fn main() -> Result<()> {
     )
     .await;
 
+    // Init endpoint health loop
+    let health_interval = dotenvy::var("ENDPOINT_HEALTH_INTERVAL")
+        .ok()
+        .and_then(|interval| match interval.parse::<u64>() {
+            Ok(0) | Err(_) => {
+                error!("Invalid endpoint health interval: {}", interval);
+                None
+            }
+            Ok(interval) => Some(interval),
+        })
+        .unwrap_or(60); // 1 minute is default
+    start_health_loop(
+        db_arc.clone(),
+        std::time::Duration::from_secs(health_interval),
+    )
+    .await;
+
     // init MailClient
     let mailclient: Arc<Option<MailClient>> = if !dotenvy::var("ARUNA_DEV_ENV")?.parse::<bool>()? {
         Arc::new(Some(MailClient::new()?))
@@ -190,6 +251,18 @@ pub async fn main() -> Result<()> {
 
     let default_endpoint = dotenvy::var("DEFAULT_DATAPROXY_ULID")?;
 
+    // Init presigned url rate limiter
+    let url_rate_limiter = match dotenvy::var("URL_RATE_LIMIT") {
+        Ok(rate) => {
+            let burst = match dotenvy::var("URL_RATE_BURST") {
+                Ok(burst) => burst.parse::<u32>()?,
+                Err(_) => 10, // 10 urls is default
+            };
+            Some(Arc::new(RateLimiter::new(rate.parse::<f64>()?, burst)?))
+        }
+        Err(_) => None,
+    };
+
     // Init server builder
     let mut builder = Server::builder()
         .http2_keepalive_interval(Some(std::time::Duration::from_secs(15)))
@@ -265,6 +338,7 @@ pub async fn main() -> Result<()> {
                     auth_arc.clone(),
                     cache_arc.clone(),
                     meilisearch_arc.clone(),
+                    url_rate_limiter,
                 )
                 .await,
             ))
@@ -61,8 +61,11 @@ impl DatabaseHandler {
         let transaction = db_client.transaction().await?;
         let transaction_client = transaction.client();
 
-        // Remove endpoint from database
+        // Fetch endpoint pubkeys which get removed with the endpoint
         let id = request.get_id()?;
+        let pubkeys = PubKey::get_by_proxy(&id, transaction_client.client()).await?;
+
+        // Remove endpoint from database
         Endpoint::delete_by_id(&id, transaction_client.client()).await?;
 
         // Remove endpoint from users/resources
@@ -74,16 +77,26 @@ impl DatabaseHandler {
 
         //ToDo Clean user/resources from endpoint
 
-        // Emit announcement notification
-        if let Err(err) = self
-            .natsio_handler
-            .register_announcement_event(AnnouncementVariant::RemoveDataProxyId(id.to_string()))
-            .await
-        {
-            // Log error, rollback transaction and return
-            log::error!("{}", err);
-            //transaction.rollback().await?;
-            return Err(anyhow::anyhow!("Notification emission failed"));
+        // Remove endpoint pubkeys from cache
+        for pubkey in &pubkeys {
+            self.cache.remove_pubkey(pubkey.id);
+        }
+
+        // Emit announcement notifications
+        let mut announcements = vec![AnnouncementVariant::RemoveDataProxyId(id.to_string())];
+        announcements.extend(
+            pubkeys
+                .iter()
+                .map(|pubkey| AnnouncementVariant::RemovePubkey(pubkey.id as i32)),
+        );
+
+        for ann in announcements {
+            if let Err(err) = self.natsio_handler.register_announcement_event(ann).await {
+                // Log error, rollback transaction and return
+                log::error!("{}", err);
+                //transaction.rollback().await?;
+                return Err(anyhow::anyhow!("Notification emission failed"));
+            }
         }
 
         Ok(())
@@ -20,6 +20,7 @@ use aws_sdk_s3::Client;
 use aws_types::region::Region;
 use diesel_ulid::DieselUlid;
 use itertools::Itertools;
+use lazy_static::lazy_static;
 use log::debug;
 use reqsign::{AwsCredential, AwsV4Signer};
 use reqwest::Method;
@@ -30,6 +31,24 @@ use tonic::transport::{Channel, ClientTlsConfig};
 use tonic::Request;
 use url::Url;
 
+// Presigned S3 (SigV4) urls can be valid for at most one week
+const MAX_PRESIGNED_URL_TTL: i64 = 604800;
+
+lazy_static! {
+    // Lifetime of presigned urls in seconds, capped to MAX_PRESIGNED_URL_TTL
+    static ref PRESIGNED_URL_TTL: i64 = presigned_url_ttl(
+        dotenvy::var("PRESIGNED_URL_TTL")
+            .ok()
+            .and_then(|var| var.parse::<i64>().ok())
+    );
+}
+
+fn presigned_url_ttl(configured: Option<i64>) -> i64 {
+    configured
+        .map(|ttl| ttl.clamp(1, MAX_PRESIGNED_URL_TTL))
+        .unwrap_or(MAX_PRESIGNED_URL_TTL)
+}
+
 pub struct PresignedUpload(pub GetUploadUrlRequest);
 pub struct PresignedDownload(pub GetDownloadUrlRequest);
 impl DatabaseHandler {
@@ -155,7 +174,7 @@ impl DatabaseHandler {
             &bucket_name,
             &key,
             &endpoint_s3_url,
-            604800,
+            *PRESIGNED_URL_TTL,
         )?;
 
         Ok((signed_url, upload_id))
@@ -497,7 +516,7 @@ impl PresignedUpload {
 /// * `bucket: &String` - Bucket name
 /// * `key: &String` - Full path of object in bucket
 /// * `endpoint: &String` - Full path of object in bucket
-/// * `duration: i64` - Full path of object in bucket
+/// * `duration: i64` - Validity of the url in seconds
 /// *
 ///
 /// ## Returns:
@@ -583,6 +602,47 @@ fn sign_download_url(
         bucket,
         key,
         endpoint,
-        604800, //Note: Default 1 week until requests allow custom duration
+        *PRESIGNED_URL_TTL,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presigned_url_ttl() {
+        // Unset ttl defaults to the maximum
+        assert_eq!(presigned_url_ttl(None), MAX_PRESIGNED_URL_TTL);
+        // Ttl within bounds is kept
+        assert_eq!(presigned_url_ttl(Some(3600)), 3600);
+        // Over-cap and invalid ttls are clamped
+        assert_eq!(
+            presigned_url_ttl(Some(MAX_PRESIGNED_URL_TTL * 2)),
+            MAX_PRESIGNED_URL_TTL
+        );
+        assert_eq!(presigned_url_ttl(Some(-1)), 1);
+
+        // Signed url carries the resolved expiry
+        let url = sign_url(
+            Method::GET,
+            "access_key",
+            "secret_key",
+            false,
+            false,
+            0,
+            None,
+            "bucket",
+            "key",
+            "localhost:1337",
+            presigned_url_ttl(Some(3600)),
+        )
+        .unwrap();
+        let expires = Url::parse(&url)
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == "X-Amz-Expires")
+            .map(|(_, value)| value.to_string());
+        assert_eq!(expires, Some("3600".to_string()));
+    }
+}
@@ -42,8 +42,17 @@ impl DatabaseHandler {
             for relation in check_relations {
                 let target_id = relation.target_pid;
                 let target = Object::get_object_with_relations(&target_id, &client).await?;
-                // Check if at least one belongs to
-                if target.inbound_belongs_to.0.len() < 2 {
+                // Check if at least one belongs to remains, including relations added
+                // in the same request (e.g. when moving a resource to a new parent)
+                let added = relations_add
+                    .internal
+                    .iter()
+                    .filter(|ir| {
+                        ir.relation_name == INTERNAL_RELATION_VARIANT_BELONGS_TO
+                            && ir.target_pid == target_id
+                    })
+                    .count();
+                if target.inbound_belongs_to.0.len() + added < 2 {
                     // if not, are there any version relations?
                     if !target
                         .outbound
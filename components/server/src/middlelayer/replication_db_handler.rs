@@ -281,6 +281,15 @@ impl DatabaseHandler {
             APIReplicationStatus::Finished => ReplicationStatus::Finished,
             APIReplicationStatus::Error => ReplicationStatus::Error,
         };
+        if let Some(current) = endpoint_info.status {
+            if !current.can_transition_to(&status) {
+                return Err(anyhow!(
+                    "Invalid replication status transition from {:?} to {:?}",
+                    current,
+                    status
+                ));
+            }
+        }
         endpoint_info.status = Some(status);
         Object::update_endpoints(endpoint_id, endpoint_info.clone(), vec![object_id], &client)
             .await?;
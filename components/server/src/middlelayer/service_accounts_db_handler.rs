@@ -103,16 +103,15 @@ impl DatabaseHandler {
                 ObjectMapping::OBJECT(_) => ResourceId::ObjectId(id.to_string()),
             }),
         });
-        let expires_at = request.0.expires_at;
         // Create token
         let (token_ulid, token) = self
             .create_token(
-                &id,
+                &service_account.id,
                 authorizer.token_handler.get_current_pubkey_serial() as i32,
                 CreateToken(CreateApiTokenRequest {
                     name: request.0.name,
                     permission,
-                    expires_at: expires_at.clone(),
+                    expires_at: request.0.expires_at,
                 }),
             )
             .await?;
@@ -130,7 +129,7 @@ impl DatabaseHandler {
         let token_secret = authorizer.token_handler.sign_user_token(
             &service_account.id,
             &token_ulid,
-            expires_at,
+            Some(token.expires_at.into()),
         )?;
 
         if let Err(err) = self
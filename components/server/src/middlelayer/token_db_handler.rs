@@ -1,9 +1,9 @@
 use crate::database::dsls::user_dsl::APIToken;
-use crate::database::dsls::user_dsl::User;
+use crate::database::dsls::user_dsl::{TokenNameInUseError, User};
 use crate::middlelayer::db_handler::DatabaseHandler;
 use crate::middlelayer::token_request_types::{CreateToken, DeleteToken};
 use ahash::HashMap;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use aruna_rust_api::api::notification::services::v2::EventVariant;
 use diesel_ulid::DieselUlid;
 
@@ -60,6 +60,11 @@ impl DatabaseHandler {
         let mut token_map: HashMap<DieselUlid, &APIToken> = HashMap::default();
         token_map.insert(token_ulid, &token);
         let user = User::add_user_token(client, user_id, token_map).await?;
+        // Concurrent creations are serialized on the user row, dropping the
+        // transaction rolls the token back
+        if user.count_tokens_named(&token.name) > 1 {
+            return Err(anyhow!(TokenNameInUseError));
+        }
         transaction.commit().await?;
 
         // Update user in cache
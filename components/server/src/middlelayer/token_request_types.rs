@@ -1,9 +1,11 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use aruna_rust_api::api::storage::services::v2::{
     CreateApiTokenRequest, DeleteApiTokenRequest, GetApiTokenRequest,
 };
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDateTime};
 use diesel_ulid::DieselUlid;
+use lazy_static::lazy_static;
+use prost_wkt_types::Timestamp;
 use std::str::FromStr;
 
 use crate::database::{
@@ -11,6 +13,17 @@ use crate::database::{
     enums::{DbPermissionLevel, ObjectMapping},
 };
 
+lazy_static! {
+    // Token lifetime in seconds if no expiry is requested (10 years by default)
+    pub static ref DEFAULT_TOKEN_TTL: i64 = dotenvy::var("DEFAULT_TOKEN_TTL")
+        .map(|var| var.parse::<i64>().unwrap_or(315360000))
+        .unwrap_or(315360000);
+    // Maximum token lifetime in seconds; unlimited if unset
+    pub static ref MAX_TOKEN_TTL: Option<i64> = dotenvy::var("MAX_TOKEN_TTL")
+        .ok()
+        .and_then(|var| var.parse::<i64>().ok());
+}
+
 #[derive(Clone)]
 pub struct CreateToken(pub CreateApiTokenRequest);
 pub struct DeleteToken(pub DeleteApiTokenRequest);
@@ -35,22 +48,43 @@ impl CreateToken {
             pub_key: pubkey_serial,
             name: self.0.name.clone(),
             created_at: chrono::Utc::now().naive_utc(),
-            expires_at: if let Some(expiration) = &self.0.expires_at {
-                DateTime::from_timestamp(expiration.seconds, 0)
-                    .map(|e| e.naive_utc())
-                    .ok_or_else(|| anyhow::anyhow!("Timestamp conversion failed"))?
-            } else {
-                // Add 10 years to token lifetime if expiry unspecified
-                DateTime::from_timestamp(chrono::Utc::now().timestamp() + 315360000, 0)
-                    .map(|e| e.naive_utc())
-                    .ok_or_else(|| anyhow::anyhow!("Timestamp conversion failed"))?
-            },
+            expires_at: token_expiry(
+                self.0.expires_at.as_ref(),
+                chrono::Utc::now().timestamp(),
+                *DEFAULT_TOKEN_TTL,
+                *MAX_TOKEN_TTL,
+            )?,
             object_id: resource_id,
             user_rights: user_right,
         })
     }
 }
 
+/// Applies the default lifetime to tokens without requested expiry and
+/// rejects requested expiries beyond the maximum lifetime.
+fn token_expiry(
+    requested: Option<&Timestamp>,
+    now: i64,
+    default_ttl: i64,
+    max_ttl: Option<i64>,
+) -> Result<NaiveDateTime> {
+    let expiry = match requested {
+        Some(expiration) => expiration.seconds,
+        None => now + default_ttl,
+    };
+    if let Some(max_ttl) = max_ttl {
+        if expiry > now + max_ttl {
+            bail!(
+                "Token expiry exceeds maximum lifetime of {} seconds",
+                max_ttl
+            );
+        }
+    }
+    DateTime::from_timestamp(expiry, 0)
+        .map(|e| e.naive_utc())
+        .ok_or_else(|| anyhow::anyhow!("Timestamp conversion failed"))
+}
+
 impl DeleteToken {
     pub fn get_token_id(&self) -> Result<DieselUlid> {
         Ok(DieselUlid::from_str(&self.0.token_id)?)
@@ -61,3 +95,38 @@ impl GetToken {
         Ok(DieselUlid::from_str(&self.0.token_id)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_expiry() {
+        let now = chrono::Utc::now().timestamp();
+
+        // Unset expiry gets default lifetime
+        let expiry = token_expiry(None, now, 3600, Some(7200)).unwrap();
+        assert_eq!(expiry.and_utc().timestamp(), now + 3600);
+
+        // Requested expiry within maximum is kept
+        let requested = Timestamp {
+            seconds: now + 7200,
+            nanos: 0,
+        };
+        let expiry = token_expiry(Some(&requested), now, 3600, Some(7200)).unwrap();
+        assert_eq!(expiry.and_utc().timestamp(), now + 7200);
+
+        // Requested expiry beyond maximum is rejected
+        let requested = Timestamp {
+            seconds: now + 7201,
+            nanos: 0,
+        };
+        assert!(token_expiry(Some(&requested), now, 3600, Some(7200)).is_err());
+
+        // Default lifetime is also bound by maximum
+        assert!(token_expiry(None, now, 7201, Some(7200)).is_err());
+
+        // Without maximum any expiry is accepted
+        assert!(token_expiry(Some(&requested), now, 3600, None).is_ok());
+    }
+}
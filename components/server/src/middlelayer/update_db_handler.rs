@@ -147,7 +147,12 @@ impl DatabaseHandler {
         }
     }
 
-    pub async fn update_keyvals(&self, request: KeyValueUpdate) -> Result<ObjectWithRelations> {
+    /// Static labels can only be removed if `is_admin` is set
+    pub async fn update_keyvals(
+        &self,
+        request: KeyValueUpdate,
+        is_admin: bool,
+    ) -> Result<ObjectWithRelations> {
         let mut client = self.database.get_client().await?;
         let transaction = client.transaction().await?;
         let transaction_client = transaction.client();
@@ -187,8 +192,8 @@ impl DatabaseHandler {
                 .await?
                 .ok_or(anyhow!("Dataset does not exist."))?;
             for kv in rm_key_values.0 {
-                if kv.variant == KeyValueVariant::STATIC_LABEL {
-                    return Err(anyhow!("Cannot remove static labels."));
+                if kv.variant == KeyValueVariant::STATIC_LABEL && !is_admin {
+                    return Err(anyhow!("Only admins can remove static labels."));
                 }
                 if kv.variant == KeyValueVariant::HOOK_STATUS {
                     return Err(anyhow!(
@@ -280,11 +285,13 @@ impl DatabaseHandler {
         }
     }
 
+    /// Static labels can only be removed if `is_admin` is set
     pub async fn update_grpc_object(
         &self,
         request: UpdateObjectRequest,
         user_id: DieselUlid,
         is_service_account: bool,
+        is_admin: bool,
     ) -> Result<(
         ObjectWithRelations,
         bool, // Creates revision
@@ -343,7 +350,7 @@ impl DatabaseHandler {
                 data_class,
                 description: req.get_description(old.clone()),
                 name: req.get_name(old.clone()),
-                key_values: Json(req.get_all_kvs(old.clone())?),
+                key_values: Json(req.get_all_kvs(old.clone(), is_admin)?),
                 hashes: Json(req.get_hashes(old.clone())?),
                 object_type: crate::database::enums::ObjectType::OBJECT,
                 object_status, // New revisions must be finished if force_revision is set
@@ -624,9 +631,23 @@ impl DatabaseHandler {
         let id = request.get_id()?;
         let mut client = self.database.get_client().await?;
         let mut object = Object::get_object_with_relations(&id, &client).await?;
-        let (to_remove, mut to_add) = request.get_authors()?;
-        object.object.authors.0.retain(|a| !to_remove.contains(a));
-        object.object.authors.0.append(&mut to_add);
+        let (to_remove, to_add) = request.get_authors()?;
+        object
+            .object
+            .authors
+            .0
+            .retain(|a| !to_remove.iter().any(|r| r.is_same_author(a)));
+        for author in to_add {
+            if !object
+                .object
+                .authors
+                .0
+                .iter()
+                .any(|a| a.is_same_author(&author))
+            {
+                object.object.authors.0.push(author);
+            }
+        }
 
         // Create transaction
         let transaction = client.transaction().await?;
@@ -291,10 +291,19 @@ impl UpdateObject {
             true => old.hashes.0,
         })
     }
-    pub fn get_all_kvs(&self, old: Object) -> Result<KeyValues> {
+    pub fn get_all_kvs(&self, old: Object, is_admin: bool) -> Result<KeyValues> {
         let rm_kv = &self.0.remove_key_values;
         let add_kv = &self.0.add_key_values;
         let remove_kv: KeyValues = rm_kv.try_into()?;
+        if !is_admin
+            && remove_kv
+                .0
+                .iter()
+                .map(|kv| &kv.variant)
+                .contains(&KeyValueVariant::STATIC_LABEL)
+        {
+            return Err(anyhow!("Only admins can remove static labels."));
+        }
         let mut add_kv: KeyValues = add_kv.try_into()?;
         if add_kv
             .0
@@ -328,7 +337,7 @@ impl UpdateObject {
         let new = self.0.data_class;
         let old_converted: i32 = old.data_class.clone().into();
         if is_service_account {
-            return if (new != 0) || (new != 4) {
+            return if (new != 0) && (new != DataClass::WORKSPACE as i32) {
                 Err(anyhow!("Workspaces need to be claimed for status updates"))
             } else {
                 Ok((DataClass::WORKSPACE, false))
@@ -175,7 +175,6 @@ impl DatabaseHandler {
     ) -> Result<User> {
         let client = self.database.get_client().await?;
         let email = request.get_email();
-
         // Update user email in database
         let user = User::update_email(&client, &user_id, email).await?;
 
@@ -410,15 +409,20 @@ impl DatabaseHandler {
         if self.cache.oidc_mapping_exists(mapping) {
             bail!("Oidc ID already registered");
         }
-        let user = self
-            .cache
-            .get_user(&user_id)
-            .ok_or_else(|| anyhow!("User not found"))?;
-
-        let mut new_attributes = user.attributes.0.clone();
-        new_attributes.external_ids.push(mapping.clone());
-        let user = User::set_user_attributes(&client, &user_id, Json(new_attributes)).await?;
+        let user = User::add_oidc_mapping(&client, &user_id, mapping)
+            .await?
+            .ok_or_else(|| anyhow!("User not found or oidc ID already registered"))?;
         self.cache.update_user(&user_id, user.clone());
+
+        // Try to emit user updated notification(s)
+        if let Err(err) = self
+            .natsio_handler
+            .register_user_event(&user, EventVariant::Updated)
+            .await
+        {
+            log::error!("{}", err);
+            return Err(anyhow::anyhow!("Notification emission failed"));
+        }
         Ok(user)
     }
 
@@ -428,20 +432,20 @@ impl DatabaseHandler {
         provider_name: &str,
     ) -> Result<User> {
         let client = self.database.get_client().await?;
-        let user = self
-            .cache
-            .get_user(&user_id)
-            .ok_or_else(|| anyhow!("User not found"))?;
+        let user = User::remove_oidc_provider(&client, &user_id, provider_name)
+            .await?
+            .ok_or_else(|| anyhow!("User not found or cannot remove last external id"))?;
+        self.cache.update_user(&user_id, user.clone());
 
-        let mut new_attributes = user.attributes.0.clone();
-        if user.attributes.0.external_ids.len() == 1 {
-            bail!("Cannot remove last external id");
+        // Try to emit user updated notification(s)
+        if let Err(err) = self
+            .natsio_handler
+            .register_user_event(&user, EventVariant::Updated)
+            .await
+        {
+            log::error!("{}", err);
+            return Err(anyhow::anyhow!("Notification emission failed"));
         }
-        new_attributes
-            .external_ids
-            .retain(|e| e.oidc_name != provider_name);
-        let user = User::set_user_attributes(&client, &user_id, Json(new_attributes)).await?;
-        self.cache.update_user(&user_id, user.clone());
         Ok(user)
     }
 
@@ -527,6 +531,11 @@ impl DatabaseHandler {
     ) -> Result<User> {
         let client = self.database.get_client().await?;
         let endpoint = DieselUlid::from_str(&request.endpoint_id)?;
+        if self.cache.has_user_data_on_endpoint(&user_id, &endpoint) {
+            return Err(anyhow::anyhow!(
+                "Endpoint still holds data of objects created by the user"
+            ));
+        }
         let user = User::remove_trusted_endpoint(&client, &user_id, &endpoint).await?;
         self.cache.update_user(&user_id, user.clone());
         // Try to emit user updated notification(s)
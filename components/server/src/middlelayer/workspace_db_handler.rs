@@ -89,7 +89,7 @@ impl DatabaseHandler {
         self.cache.add_user(service_user.id, service_user.clone());
 
         // Create token
-        let (token_ulid, _) = self
+        let (token_ulid, token) = self
             .create_token(
                 &service_user.id,
                 authorizer.token_handler.get_current_pubkey_serial() as i32,
@@ -146,10 +146,11 @@ impl DatabaseHandler {
             .await?;
 
         // Sign token
-        let token_secret =
-            authorizer
-                .token_handler
-                .sign_user_token(&service_user.id, &token_ulid, None)?;
+        let token_secret = authorizer.token_handler.sign_user_token(
+            &service_user.id,
+            &token_ulid,
+            Some(token.expires_at.into()),
+        )?;
 
         // Create creds
         let slt = authorizer.token_handler.sign_dataproxy_slt(
@@ -271,8 +272,8 @@ impl DatabaseHandler {
         for res in &mut resources {
             // - Change created_by to user
             res.object.created_by = user_id;
-            // - Change DataClass from workspace to confidential
-            res.object.data_class = DataClass::CONFIDENTIAL;
+            // - Change DataClass from workspace to private, same as in batch_claim
+            res.object.data_class = DataClass::PRIVATE;
             self.cache.upsert_object(&res.object.id, res.clone());
         }
         let user = User::get(user_id, &client)
@@ -51,7 +51,8 @@ pub trait EventHandler {
     ) -> anyhow::Result<Vec<Message>>;
 
     // Acknowledge messages as read so they will not be redelivered by consecutive message fetches.
-    async fn acknowledge_from_reply(&self, replies: Vec<Reply>) -> anyhow::Result<()>;
+    // Replies which could not be validated are routed to the dead-letter subject and returned.
+    async fn acknowledge_from_reply(&self, replies: Vec<Reply>) -> anyhow::Result<Vec<Reply>>;
 
     // Creates an event stream handler depending on the underlying system
     // The handler is connected to a stream group to load-balance messages... !?
@@ -13,7 +13,10 @@ use aruna_rust_api::api::storage::models::v2::{ResourceVariant, User as ApiUser}
 use async_nats::jetstream::consumer::{pull, Config, DeliverPolicy, PullConsumer};
 use async_nats::HeaderMap;
 
-use async_nats::jetstream::{stream::Stream, Context, Message};
+use async_nats::jetstream::{
+    stream::{DiscardPolicy, Stream},
+    Context, Message,
+};
 
 use async_trait::async_trait;
 use diesel_ulid::DieselUlid;
@@ -31,8 +34,9 @@ use crate::utils::grpc_utils::{checksum_resource, checksum_user, generic_object_
 use super::handler::{EventHandler, EventStreamHandler, EventType};
 use super::utils::{
     generate_announcement_message_subject, generate_announcement_subject,
-    generate_endpoint_subject, generate_resource_message_subjects, generate_resource_subject,
-    generate_user_message_subject, generate_user_subject, validate_reply_msg,
+    generate_dead_letter_subject, generate_endpoint_subject, generate_resource_message_subjects,
+    generate_resource_subject, generate_user_message_subject, generate_user_subject,
+    validate_reply_msg,
 };
 
 // ----- Constants used for notifications -------------------- //
@@ -44,6 +48,15 @@ pub const STREAM_SUBJECTS: [&str; 5] = [
     "AOS.ENDPOINT.>",
     "AOS.SERVER.>",
 ];
+// Kept outside of AOS.> so dead letters never reach event consumers
+pub const DLQ_STREAM_NAME: &str = "AOS_DLQ_STREAM";
+pub const DLQ_STREAM_SUBJECTS: [&str; 1] = ["DLQ.>"];
+// Retention of the dead-letter stream, the oldest dead letters are discarded first
+pub const DLQ_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+pub const DLQ_MAX_BYTES: i64 = 100 * 1024 * 1024;
+pub const DLQ_MAX_MESSAGES: i64 = 100_000;
+// Invalid replies of a single batch beyond this are dropped instead of dead-lettered
+pub const MAX_DEAD_LETTERS_PER_BATCH: usize = 10;
 
 #[derive(Deserialize, Serialize)]
 // Enum for internal events that are only of interest for the ArunaServer instances
@@ -84,16 +97,24 @@ pub enum Deleted {
     },
 }
 
+pub const DEFAULT_FETCH_MAX_BYTES: usize = 0; // Unlimited
+pub const DEFAULT_FETCH_EXPIRY_MS: u64 = 250;
+
 // ----------------------------------------------------------- //
 pub struct NatsIoHandler {
     jetstream_context: Context,
     stream: Stream,
+    dlq_stream: Stream,
     pub reply_secret: String,
+    pub max_bytes: usize,
+    pub expiry_ms: u64,
 }
 
 #[derive(Debug, Clone)]
 pub struct NatsIOEventStreamHandler {
     pub consumer: PullConsumer,
+    pub max_bytes: usize,
+    pub expiry_ms: u64,
 }
 
 #[async_trait::async_trait]
@@ -180,6 +201,7 @@ impl EventHandler for NatsIoHandler {
         let mut fetch = consumer
             .fetch()
             .max_messages(i64::MAX as usize)
+            .max_bytes(self.max_bytes)
             .messages()
             .await?;
 
@@ -209,18 +231,31 @@ impl EventHandler for NatsIoHandler {
         Ok(messages)
     }
 
-    //ToDo: Rust Doc
-    async fn acknowledge_from_reply(&self, replies: Vec<Reply>) -> anyhow::Result<()> {
+    /// Acknowledges all replies with a valid hmac signature. Replies which fail the
+    /// signature validation are published to the dead-letter subject instead of
+    /// failing the whole batch, at most `MAX_DEAD_LETTERS_PER_BATCH` per call.
+    ///
+    /// ## Arguments:
+    /// * `replies` - Replies of previously fetched messages
+    ///
+    /// ## Returns:
+    /// * `anyhow::Result<Vec<Reply>>` - The replies which were routed to the dead-letter subject
+    async fn acknowledge_from_reply(&self, replies: Vec<Reply>) -> anyhow::Result<Vec<Reply>> {
         // Create vector to collect Nats.io acknowledge replies
-        let mut reply_ack = Vec::new();
+        let mut valid_replies = Vec::new();
+        let mut dead_letters = Vec::new();
         for reply in replies {
             // Validate reply hmac
             match validate_reply_msg(reply.clone(), self.reply_secret.clone()) {
                 Ok(hmac_matches) => {
                     if !hmac_matches {
-                        return Err(anyhow::anyhow!(
-                            "Message hmac signature did not match original signature"
-                        ));
+                        log::warn!("Message hmac signature did not match original signature");
+                        if dead_letters.len() < MAX_DEAD_LETTERS_PER_BATCH {
+                            dead_letters.push(reply);
+                        } else {
+                            log::warn!("Dead-letter limit of batch reached, dropping reply");
+                        }
+                        continue;
                     }
                 }
                 Err(err) => {
@@ -230,14 +265,36 @@ impl EventHandler for NatsIoHandler {
                 }
             }
 
-            // Acknowledge message in Nats.io
-            reply_ack.push(
-                self.jetstream_context
-                    .publish(reply.reply.clone(), "".into()),
-            );
+            valid_replies.push(reply);
+        }
+
+        // Route invalid replies to the dead-letter subject before acknowledging
+        // anything, so a failure does not leave a partially applied batch
+        for dead_letter in &dead_letters {
+            let message_bytes = Bytes::from(serde_json::to_string_pretty(dead_letter)?);
+            let published = match self
+                .jetstream_context
+                .publish(generate_dead_letter_subject(), message_bytes)
+                .await
+            {
+                Ok(ack_future) => ack_future.await.map(|_| ()).map_err(|e| anyhow!(e)),
+                Err(err) => Err(anyhow!(err)),
+            };
+            if let Err(err) = published {
+                log::error!("{}", err);
+                return Err(anyhow::anyhow!(
+                    "Could not publish reply to dead-letter queue"
+                ));
+            }
         }
 
-        // Check if all messages could be acknowledged
+        // Acknowledge messages in Nats.io
+        let reply_ack = valid_replies.iter().map(|reply| {
+            self.jetstream_context
+                .publish(reply.reply.clone(), "".into())
+        });
+
+        // Check if all valid messages could be acknowledged
         match try_join_all(reply_ack).await {
             Ok(_) => {}
             Err(err) => {
@@ -248,7 +305,7 @@ impl EventHandler for NatsIoHandler {
             }
         }
 
-        return Ok(());
+        Ok(dead_letters)
     }
 
     ///ToDo: Rust Doc
@@ -264,7 +321,10 @@ impl EventHandler for NatsIoHandler {
             .map_err(|err| anyhow::anyhow!(err))?;
 
         // Create and return event stream handler
-        let stream_handler = Box::new(NatsIOEventStreamHandler { consumer });
+        let stream_handler = Box::new(
+            NatsIOEventStreamHandler::new(consumer)
+                .with_fetch_limits(self.max_bytes, self.expiry_ms),
+        );
 
         return Ok(stream_handler);
     }
@@ -354,17 +414,72 @@ impl NatsIoHandler {
         };
 
         // Create stream to publish messages
-        let stream = jetstream_context
-            .get_or_create_stream(stream_config)
+        let mut stream = jetstream_context
+            .get_or_create_stream(stream_config.clone())
             .await?;
 
+        // Existing streams keep their subjects, sync them with the current ones
+        let mut current_config = stream.info().await?.config.clone();
+        if current_config.subjects != stream_config.subjects {
+            current_config.subjects = stream_config.subjects;
+            jetstream_context.update_stream(&current_config).await?;
+            stream = jetstream_context.get_stream(&stream_name).await?;
+        }
+
+        // Create separate stream for dead letters with bounded retention
+        let dlq_config = async_nats::jetstream::stream::Config {
+            name: DLQ_STREAM_NAME.to_string(),
+            subjects: DLQ_STREAM_SUBJECTS
+                .into_iter()
+                .map(|subject| subject.into())
+                .collect(),
+            max_age: DLQ_MAX_AGE,
+            max_bytes: DLQ_MAX_BYTES,
+            max_messages: DLQ_MAX_MESSAGES,
+            discard: DiscardPolicy::Old,
+            ..Default::default()
+        };
+        let mut dlq_stream = jetstream_context
+            .get_or_create_stream(dlq_config.clone())
+            .await?;
+
+        // Streams created without limits are updated to the current ones
+        let mut current_config = dlq_stream.info().await?.config.clone();
+        if current_config.max_age != dlq_config.max_age
+            || current_config.max_bytes != dlq_config.max_bytes
+            || current_config.max_messages != dlq_config.max_messages
+            || current_config.discard != dlq_config.discard
+        {
+            current_config.max_age = dlq_config.max_age;
+            current_config.max_bytes = dlq_config.max_bytes;
+            current_config.max_messages = dlq_config.max_messages;
+            current_config.discard = dlq_config.discard;
+            jetstream_context.update_stream(&current_config).await?;
+            dlq_stream = jetstream_context.get_stream(DLQ_STREAM_NAME).await?;
+        }
+
         Ok(NatsIoHandler {
             jetstream_context,
             stream,
+            dlq_stream,
             reply_secret: secret,
+            max_bytes: DEFAULT_FETCH_MAX_BYTES,
+            expiry_ms: DEFAULT_FETCH_EXPIRY_MS,
         })
     }
 
+    /// Limits the size of message fetches. The expiry is passed on to all
+    /// event stream handlers created by this handler.
+    ///
+    /// ## Arguments:
+    /// * `max_bytes` - Maximum number of bytes per fetch; 0 means unlimited
+    /// * `expiry_ms` - Time in milliseconds a batch request waits for messages
+    pub fn with_fetch_limits(mut self, max_bytes: usize, expiry_ms: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self.expiry_ms = expiry_ms;
+        self
+    }
+
     ///ToDo: Rust Doc
     pub async fn get_pull_consumer(
         &self,
@@ -410,6 +525,26 @@ impl NatsIoHandler {
         //Ok((consumer_id, consumer_config))
     }
 
+    /// Creates an ephemeral consumer for all dead-lettered replies
+    pub async fn create_dead_letter_consumer(
+        &self,
+        delivery_policy: DeliverPolicy,
+    ) -> anyhow::Result<PullConsumer> {
+        let consumer_id = DieselUlid::generate().to_string();
+        Ok(self
+            .dlq_stream
+            .get_or_create_consumer(
+                &consumer_id,
+                pull::Config {
+                    name: Some(consumer_id.clone()),
+                    deliver_policy: delivery_policy,
+                    inactive_threshold: Duration::from_secs(86400),
+                    ..Default::default()
+                },
+            )
+            .await?)
+    }
+
     /// Only send event to a specific dataproxy
     pub async fn register_dataproxy_event(
         &self,
@@ -589,6 +724,24 @@ impl NatsIoHandler {
     }
 }
 
+impl NatsIOEventStreamHandler {
+    /// Creates an event stream handler with the default fetch limits
+    pub fn new(consumer: PullConsumer) -> Self {
+        NatsIOEventStreamHandler {
+            consumer,
+            max_bytes: DEFAULT_FETCH_MAX_BYTES,
+            expiry_ms: DEFAULT_FETCH_EXPIRY_MS,
+        }
+    }
+
+    /// Overrides the maximum bytes and expiry of batch requests
+    pub fn with_fetch_limits(mut self, max_bytes: usize, expiry_ms: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self.expiry_ms = expiry_ms;
+        self
+    }
+}
+
 #[async_trait]
 impl EventStreamHandler for NatsIOEventStreamHandler {
     async fn get_event_consumer_messages(
@@ -599,8 +752,9 @@ impl EventStreamHandler for NatsIOEventStreamHandler {
         let mut batch = self
             .consumer
             .batch()
-            .expires(Duration::from_millis(250))
+            .expires(Duration::from_millis(self.expiry_ms))
             .max_messages(max_batch_size as usize)
+            .max_bytes(self.max_bytes)
             .messages()
             .await?;
 
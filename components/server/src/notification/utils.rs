@@ -100,6 +100,11 @@ pub fn generate_endpoint_subject(endpoint_id: &DieselUlid) -> String {
     format!("AOS.ENDPOINT.{}", endpoint_id)
 }
 
+/// Subject for replies which could not be acknowledged due to a failed hmac validation
+pub fn generate_dead_letter_subject() -> String {
+    "DLQ.REPLY".to_string()
+}
+
 ///ToDo: Rust Doc
 pub fn parse_event_consumer_subject(subject: &str) -> anyhow::Result<EventType> {
     // Evaluate general message variant
@@ -15,7 +15,7 @@ use meilisearch_sdk::{
 };
 use prost_wkt_types::Timestamp;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{fmt::Display, str::FromStr};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
 
 // Enum for the different index variants (multi-index search?)
 #[derive(Serialize)]
@@ -530,8 +530,29 @@ impl MeilisearchClient {
                 _ => bail!("Search index creation failed: Could not set sortable attributes"),
             };
 
-            //TODO: Exclude fields from search?
-            //index.set_searchable_attributes(&[]).await?;
+            // Set the searchable attributes of the index. The order defines the
+            // attribute ranking, i.e. title matches outrank description matches.
+            match index
+                .set_searchable_attributes([
+                    "name",
+                    "title",
+                    "description",
+                    "labels",
+                    "authors",
+                    "id",
+                    "object_type",
+                    "status",
+                    "data_class",
+                    "metadata_license",
+                    "data_license",
+                ])
+                .await?
+                .wait_for_completion(&self.client, None, None)
+                .await?
+            {
+                Task::Succeeded { .. } => {}
+                _ => bail!("Search index creation failed: Could not set searchable attributes"),
+            };
 
             // Set pagination configuration
             match index
@@ -550,6 +571,55 @@ impl MeilisearchClient {
         })
     }
 
+    /// Replaces the stop words of the index, i.e. words ignored in queries.
+    pub async fn set_stop_words(
+        &self,
+        index_name: &str,
+        stop_words: &[String],
+    ) -> anyhow::Result<()> {
+        match self
+            .client
+            .index(index_name)
+            .set_stop_words(stop_words)
+            .await?
+            .wait_for_completion(&self.client, None, None)
+            .await?
+        {
+            Task::Succeeded { .. } => Ok(()),
+            _ => bail!("Could not set stop words of search index"),
+        }
+    }
+
+    /// Replaces the synonyms of the index. All words of a group are treated
+    /// as synonyms of each other.
+    pub async fn set_synonyms(
+        &self,
+        index_name: &str,
+        synonym_groups: &[Vec<String>],
+    ) -> anyhow::Result<()> {
+        let mut synonyms: HashMap<String, Vec<String>> = HashMap::new();
+        for group in synonym_groups {
+            for word in group {
+                synonyms
+                    .entry(word.to_string())
+                    .or_default()
+                    .extend(group.iter().filter(|synonym| *synonym != word).cloned());
+            }
+        }
+
+        match self
+            .client
+            .index(index_name)
+            .set_synonyms(&synonyms)
+            .await?
+            .wait_for_completion(&self.client, None, None)
+            .await?
+        {
+            Task::Succeeded { .. } => Ok(()),
+            _ => bail!("Could not set synonyms of search index"),
+        }
+    }
+
     ///ToDo: Rust Doc
     pub async fn delete_index(&self, index: MeilisearchIndexes) -> anyhow::Result<()> {
         // Extract index name of enum variant
@@ -421,6 +421,10 @@ impl TryFrom<Author> for DBAuthor {
         } else {
             None
         };
+        if let Some(email) = &value.email {
+            lettre::Address::from_str(email)
+                .map_err(|_| anyhow::anyhow!("Invalid author email: {}", email))?;
+        }
         Ok(DBAuthor {
             first_name: value.first_name,
             last_name: value.last_name,
@@ -140,11 +140,13 @@ impl InternalRelation {
                 //    .get_object(&DieselUlid::from_str(&api_rel.resource_id)?)
                 //    .ok_or_else(|| anyhow!("other_obj not found"))?;
 
-                if self_obj.object.object_type == other_obj.object.object_type
+                // Parents must be strictly higher in the hierarchy, which also
+                // prevents BelongsTo cycles when resources get moved
+                if other_obj.object.object_type >= self_obj.object.object_type
                     && api_rel.defined_variant == InternalRelationVariant::BelongsTo as i32
                 {
                     return Err(anyhow!(
-                        "Can not assign BelongsTo relation to same-level hierarchy object"
+                        "BelongsTo relations must point to a lower-level hierarchy object"
                     ));
                 }
 
@@ -175,11 +177,11 @@ impl InternalRelation {
                 //     .get_object(&DieselUlid::from_str(&api_rel.resource_id)?)
                 //     .ok_or_else(|| anyhow!("other_obj not found"))?;
 
-                if self_obj.object.object_type == other_obj.object.object_type
+                if other_obj.object.object_type >= self_obj.object.object_type
                     && api_rel.defined_variant == InternalRelationVariant::BelongsTo as i32
                 {
                     return Err(anyhow!(
-                        "Can not assign BelongsTo relation to same-level hierarchy object"
+                        "BelongsTo relations must point to a lower-level hierarchy object"
                     ));
                 }
 
@@ -31,6 +31,25 @@ pub fn type_name_of<T>(_: T) -> &'static str {
     std::any::type_name::<T>()
 }
 
+/// Converts errors of authorization steps into an unauthenticated status.
+/// Errors which already are a status keep their code, e.g. to distinguish
+/// missing resources from missing permissions.
+pub trait IntoAuthStatus {
+    fn into_auth_status(self, message: &str) -> Status;
+}
+
+impl IntoAuthStatus for Status {
+    fn into_auth_status(self, message: &str) -> Status {
+        Status::new(self.code(), format!("{} : {}", message, self.message()))
+    }
+}
+
+impl IntoAuthStatus for anyhow::Error {
+    fn into_auth_status(self, message: &str) -> Status {
+        Status::unauthenticated(format!("{} : {}", message, self))
+    }
+}
+
 pub trait IntoGenericInner<T> {
     fn into_inner(self) -> Result<T, Status>;
 }
@@ -3,4 +3,5 @@ pub mod conversions;
 pub mod database_utils;
 pub mod grpc_utils;
 pub mod mailclient;
+pub mod rate_limiter;
 pub mod search_utils;
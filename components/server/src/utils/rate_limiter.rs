@@ -0,0 +1,154 @@
+use anyhow::{bail, Result};
+use dashmap::DashMap;
+use diesel_ulid::DieselUlid;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter keyed by token (or user) id
+pub struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: DashMap<DieselUlid, Bucket>,
+    // Buckets untouched for this long are full again and can be dropped
+    refill_time: Duration,
+    last_prune: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// `rate` is the refill in requests per second, `burst` the bucket capacity
+    pub fn new(rate: f64, burst: u32) -> Result<Self> {
+        if !rate.is_finite() || rate <= 0.0 {
+            bail!("Rate limit must be a positive number, got {}", rate)
+        }
+        if burst == 0 {
+            bail!("Rate limit burst must be at least 1")
+        }
+        let burst = f64::from(burst);
+        Ok(RateLimiter {
+            rate,
+            burst,
+            buckets: DashMap::new(),
+            refill_time: Duration::try_from_secs_f64(burst / rate).unwrap_or(Duration::MAX),
+            last_prune: Mutex::new(Instant::now()),
+        })
+    }
+
+    pub fn check(&self, key: &DieselUlid) -> Result<()> {
+        self.check_at(key, Instant::now())
+    }
+
+    fn check_at(&self, key: &DieselUlid, now: Instant) -> Result<()> {
+        self.prune(now);
+        let mut bucket = self.buckets.entry(*key).or_insert(Bucket {
+            tokens: self.burst,
+            last_refill: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(self.burst);
+        bucket.last_refill = now;
+        if bucket.tokens < 1.0 {
+            bail!("Rate limit exceeded")
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+
+    /// Drops refilled buckets at most once per refill time, a full bucket
+    /// behaves exactly like a missing one
+    fn prune(&self, now: Instant) {
+        let Ok(mut last_prune) = self.last_prune.try_lock() else {
+            return;
+        };
+        if now.saturating_duration_since(*last_prune) < self.refill_time {
+            return;
+        }
+        *last_prune = now;
+        drop(last_prune);
+        self.buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            bucket.tokens + elapsed.as_secs_f64() * self.rate < self.burst
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(2.0, 3).unwrap();
+        let token = DieselUlid::generate();
+        let other = DieselUlid::generate();
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at(&token, start).is_ok());
+        }
+        assert!(limiter.check_at(&token, start).is_err());
+        // Buckets are per key
+        assert!(limiter.check_at(&other, start).is_ok());
+
+        // One request is refilled every 500ms
+        assert!(limiter
+            .check_at(&token, start + Duration::from_millis(250))
+            .is_err());
+        assert!(limiter
+            .check_at(&token, start + Duration::from_millis(500))
+            .is_ok());
+        assert!(limiter
+            .check_at(&token, start + Duration::from_millis(500))
+            .is_err());
+
+        // Refills are capped at the burst size
+        let later = start + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert!(limiter.check_at(&token, later).is_ok());
+        }
+        assert!(limiter.check_at(&token, later).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_prunes_full_buckets() {
+        let limiter = RateLimiter::new(2.0, 3).unwrap();
+        let (idle, busy, new) = (
+            DieselUlid::generate(),
+            DieselUlid::generate(),
+            DieselUlid::generate(),
+        );
+        let start = *limiter.last_prune.lock().unwrap();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at(&idle, start).is_ok());
+        }
+        // Buckets refill within 1.5s, drained ones are kept
+        let later = start + Duration::from_secs(2);
+        for _ in 0..3 {
+            assert!(limiter.check_at(&busy, later).is_ok());
+        }
+        assert_eq!(limiter.buckets.len(), 1);
+        assert!(limiter.buckets.contains_key(&busy));
+
+        // No prune before the next refill time passed
+        assert!(limiter
+            .check_at(&new, later + Duration::from_secs(1))
+            .is_ok());
+        assert_eq!(limiter.buckets.len(), 2);
+        assert!(limiter
+            .check_at(&busy, later + Duration::from_secs(1))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rate_limiter_rejects_invalid_config() {
+        assert!(RateLimiter::new(0.0, 3).is_err());
+        assert!(RateLimiter::new(-1.0, 3).is_err());
+        assert!(RateLimiter::new(f64::NAN, 3).is_err());
+        assert!(RateLimiter::new(1.0, 0).is_err());
+    }
+}
@@ -8,6 +8,23 @@ use diesel_ulid::DieselUlid;
 use itertools::Itertools;
 use std::sync::Arc;
 
+/// Parses a comma separated list of stop words, e.g. `"the,a,of"`
+pub fn parse_stop_words(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|word| word.trim().to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Parses semicolon separated groups of comma separated synonyms,
+/// e.g. `"genome,genomic;rna,ribonucleic"`
+pub fn parse_synonym_groups(raw: &str) -> Vec<Vec<String>> {
+    raw.split(';')
+        .map(parse_stop_words)
+        .filter(|group| group.len() > 1)
+        .collect()
+}
+
 /// Removes the specific resources from the search index
 pub async fn remove_from_search_index(
     search_client: &Arc<MeilisearchClient>,
@@ -63,12 +80,12 @@ pub async fn update_search_index(
 }
 
 /// Fetches all Objects from the database and full syncs the search index in
-/// chunks of 100.000 elements.
+/// chunks of 100.000 elements. Returns the number of indexed documents.
 pub async fn full_sync_search_index(
     database_conn: Arc<Database>,
     cache: Arc<Cache>,
     search_client: Arc<MeilisearchClient>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<usize> {
     let client = database_conn.get_client().await?; // No transaction; only read
     let filtered_objects: Vec<ObjectDocument> = Object::all(&client)
         .await?
@@ -88,8 +105,10 @@ pub async fn full_sync_search_index(
     for chunk in filtered_objects.chunks(100000) {
         search_client
             .add_or_update_stuff::<ObjectDocument>(chunk, MeilisearchIndexes::OBJECT)
+            .await?
+            .wait_for_completion(&search_client.client, None, None)
             .await?;
     }
 
-    Ok(())
+    Ok(filtered_objects.len())
 }
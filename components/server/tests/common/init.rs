@@ -18,6 +18,7 @@ use aruna_server::hooks::hook_handler::HookMessage;
 use aruna_server::middlelayer::db_handler::DatabaseHandler;
 use aruna_server::notification::natsio_handler::NatsIoHandler;
 use aruna_server::search::meilisearch_client::{MeilisearchClient, MeilisearchIndexes};
+use aruna_server::utils::rate_limiter::RateLimiter;
 use async_channel::Sender;
 use std::sync::Arc;
 
@@ -418,9 +419,10 @@ pub async fn init_object_service_manual(
     auth: Arc<PermissionHandler>,
     cache: Arc<Cache>,
     search: Arc<MeilisearchClient>,
+    url_rate_limiter: Option<Arc<RateLimiter>>,
 ) -> ObjectServiceImpl {
     // Init collection service
-    ObjectServiceImpl::new(db, auth, cache, search).await
+    ObjectServiceImpl::new(db, auth, cache, search, url_rate_limiter).await
 }
 
 #[allow(dead_code)]
@@ -513,6 +515,7 @@ pub async fn init_grpc_services() -> (
             auth.clone(),
             cache.clone(),
             search.clone(),
+            None,
         )
         .await,
         init_relation_service_manual(db_handler, auth, cache, search).await,
@@ -595,6 +598,7 @@ pub async fn init_service_block() -> ServiceBlock {
             auth_handler.clone(),
             cache.clone(),
             search_handler.clone(),
+            None,
         )
         .await,
         search_service: init_search_service_manual(
@@ -70,7 +70,7 @@ pub fn new_user(object_ids: Vec<ObjectMapping<DieselUlid>>) -> User {
         display_name: "test1".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "test2@test3".to_string(),
+        email: format!("{}@test.org", rand_string(16)),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: false,
@@ -2,9 +2,12 @@ use crate::common::{init, test_utils};
 use aruna_server::database::dsls::internal_relation_dsl::InternalRelation;
 use aruna_server::database::dsls::license_dsl::ALL_RIGHTS_RESERVED;
 use aruna_server::database::dsls::object_dsl::{
-    DefinedVariant, EndpointInfo, ExternalRelation, Hierarchy, KeyValue, KeyValueVariant,
+    Algorithm, DefinedVariant, EndpointInfo, ExternalRelation, Hash, Hashes, Hierarchy, KeyValue,
+    KeyValueVariant,
+};
+use aruna_server::database::enums::{
+    DataClass, ObjectStatus, ObjectType, ReplicationStatus, ReplicationType,
 };
-use aruna_server::database::enums::{DataClass, ObjectStatus, ObjectType, ReplicationStatus};
 use aruna_server::database::{
     crud::CrudDb,
     dsls::object_dsl::{ExternalRelations, KeyValues, Object, ObjectWithRelations},
@@ -880,3 +883,137 @@ async fn add_remove_endpoint_test() {
         assert!(resource.endpoints.0.is_empty());
     }
 }
+
+#[tokio::test]
+async fn get_objects_by_hash_test() {
+    let db = init::init_database().await;
+    let client = db.get_client().await.unwrap();
+
+    let mut user = test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+
+    let shared = Hash {
+        alg: Algorithm::SHA256,
+        hash: test_utils::rand_string(64),
+    };
+    let mut first = test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::OBJECT);
+    first.hashes = Json(Hashes(vec![shared.clone()]));
+    first.create(&client).await.unwrap();
+    let mut second = test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::OBJECT);
+    second.hashes = Json(Hashes(vec![
+        Hash {
+            alg: Algorithm::MD5,
+            hash: test_utils::rand_string(32),
+        },
+        shared.clone(),
+    ]));
+    second.create(&client).await.unwrap();
+    let mut other = test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::OBJECT);
+    other.hashes = Json(Hashes(vec![Hash {
+        alg: Algorithm::SHA256,
+        hash: test_utils::rand_string(64),
+    }]));
+    other.create(&client).await.unwrap();
+
+    let duplicates = Object::get_objects_by_hash(&shared, &client).await.unwrap();
+    assert_eq!(duplicates.len(), 2);
+    assert!(duplicates.contains(&first));
+    assert!(duplicates.contains(&second));
+}
+
+#[test]
+fn get_sync_status_test() {
+    let mut object = test_utils::new_object(
+        DieselUlid::generate(),
+        DieselUlid::generate(),
+        ObjectType::OBJECT,
+    );
+    let set_statuses = |object: &mut Object, statuses: &[ReplicationStatus]| {
+        object.endpoints = Json(DashMap::from_iter(statuses.iter().map(|status| {
+            (
+                DieselUlid::generate(),
+                EndpointInfo {
+                    replication: ReplicationType::FullSync,
+                    status: Some(*status),
+                },
+            )
+        })));
+    };
+
+    set_statuses(
+        &mut object,
+        &[ReplicationStatus::Finished, ReplicationStatus::Finished],
+    );
+    assert_eq!(object.get_sync_status(), Some(ReplicationStatus::Finished));
+
+    set_statuses(
+        &mut object,
+        &[ReplicationStatus::Waiting, ReplicationStatus::Waiting],
+    );
+    assert_eq!(object.get_sync_status(), Some(ReplicationStatus::Waiting));
+
+    set_statuses(
+        &mut object,
+        &[
+            ReplicationStatus::Finished,
+            ReplicationStatus::Running,
+            ReplicationStatus::Waiting,
+        ],
+    );
+    assert_eq!(object.get_sync_status(), Some(ReplicationStatus::Running));
+
+    set_statuses(
+        &mut object,
+        &[ReplicationStatus::Finished, ReplicationStatus::Waiting],
+    );
+    assert_eq!(object.get_sync_status(), Some(ReplicationStatus::Running));
+
+    set_statuses(
+        &mut object,
+        &[
+            ReplicationStatus::Finished,
+            ReplicationStatus::Error,
+            ReplicationStatus::Running,
+        ],
+    );
+    assert_eq!(object.get_sync_status(), Some(ReplicationStatus::Error));
+
+    set_statuses(&mut object, &[]);
+    assert_eq!(object.get_sync_status(), None);
+}
+
+#[test]
+fn is_downloadable_test() {
+    let mut object = test_utils::new_object(
+        DieselUlid::generate(),
+        DieselUlid::generate(),
+        ObjectType::OBJECT,
+    );
+    let set_status = |object: &mut Object, status: ReplicationStatus| {
+        object.endpoints = Json(DashMap::from_iter([(
+            DieselUlid::generate(),
+            EndpointInfo {
+                replication: ReplicationType::FullSync,
+                status: Some(status),
+            },
+        )]));
+    };
+
+    // Uploaded data is not yet at any endpoint
+    object.object_status = ObjectStatus::INITIALIZING;
+    set_status(&mut object, ReplicationStatus::Waiting);
+    assert!(!object.is_downloadable());
+
+    // Finished at an endpoint but still being validated
+    object.object_status = ObjectStatus::VALIDATING;
+    set_status(&mut object, ReplicationStatus::Finished);
+    assert!(!object.is_downloadable());
+
+    // Available but not synced to any endpoint
+    object.object_status = ObjectStatus::AVAILABLE;
+    set_status(&mut object, ReplicationStatus::Running);
+    assert!(!object.is_downloadable());
+
+    set_status(&mut object, ReplicationStatus::Finished);
+    assert!(object.is_downloadable());
+}
@@ -1,6 +1,10 @@
 use crate::common::init;
 use aruna_server::database::crud::CrudDb;
+use aruna_server::database::dsls::endpoint_dsl::{Endpoint, HostConfigs};
 use aruna_server::database::dsls::pub_key_dsl::PubKey;
+use aruna_server::database::enums::{EndpointStatus, EndpointVariant};
+use diesel_ulid::DieselUlid;
+use postgres_types::Json;
 use tokio_postgres::GenericClient;
 
 #[tokio::test]
@@ -81,3 +85,54 @@ async fn test_pub_key_serial_auto_incerement() {
         assert_eq!(fetched_key, key)
     }
 }
+
+#[tokio::test]
+async fn test_pub_key_claimed_by_other_node() {
+    // Init database connection
+    let db = init::init_database().await;
+    let client = db.get_client().await.unwrap();
+    let client = client.client();
+
+    // Create two endpoints
+    let mut endpoints = vec![];
+    for name in ["pubkey_claim_one", "pubkey_claim_two"] {
+        let mut endpoint = Endpoint {
+            id: DieselUlid::generate(),
+            name: name.to_string(),
+            host_config: Json(HostConfigs(Vec::new())),
+            endpoint_variant: EndpointVariant::PERSISTENT,
+            documentation_object: None,
+            is_public: false,
+            status: EndpointStatus::INITIALIZING,
+        };
+        endpoint.create(client).await.unwrap();
+        endpoints.push(endpoint);
+    }
+    let dummy_pubkey = "MCowBQYDK2VwAyEAL1cTmSs15sXdMmx9gvHOgLv4Pn6b4hmVotc7Zw6Lz8s=";
+
+    // First endpoint claims the key
+    let claimed = PubKey::create_or_get_without_id(Some(endpoints[0].id), dummy_pubkey, client)
+        .await
+        .unwrap();
+
+    // Registering the same key again for the same endpoint returns the same serial
+    let same = PubKey::create_or_get_without_id(Some(endpoints[0].id), dummy_pubkey, client)
+        .await
+        .unwrap();
+    assert_eq!(same, claimed);
+
+    // Registering the same key for another endpoint or the server is rejected
+    assert!(
+        PubKey::create_or_get_without_id(Some(endpoints[1].id), dummy_pubkey, client)
+            .await
+            .is_err()
+    );
+    assert!(PubKey::create_or_get_without_id(None, dummy_pubkey, client)
+        .await
+        .is_err());
+
+    // Cleanup; pubkey is removed with its endpoint
+    for endpoint in endpoints {
+        endpoint.delete(client).await.unwrap();
+    }
+}
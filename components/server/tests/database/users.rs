@@ -2,15 +2,18 @@ use std::{collections::HashMap, str::FromStr};
 
 use crate::common::{
     init,
-    test_utils::{ADMIN_USER_ULID, USER1_ULID, USER2_ULID},
+    test_utils::{self, ADMIN_USER_ULID, USER1_ULID, USER2_ULID},
 };
 use aruna_server::database::{
+    connection::check_unique_emails,
     crud::CrudDb,
     dsls::{
         persistent_notification_dsl::{
             NotificationReference, NotificationReferences, PersistentNotification,
         },
-        user_dsl::{APIToken, User, UserAttributes},
+        user_dsl::{
+            APIToken, EmailInUseError, OIDCMapping, OIDCMappingInUseError, User, UserAttributes,
+        },
     },
     enums::{
         DbPermissionLevel, NotificationReferenceType, ObjectMapping, PersistentNotificationVariant,
@@ -34,7 +37,7 @@ async fn create_user_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: false,
@@ -74,7 +77,7 @@ async fn update_user_name_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -119,7 +122,7 @@ async fn update_user_email_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -137,7 +140,8 @@ async fn update_user_email_test() {
     user.create(&client).await.unwrap();
 
     // Update user email
-    User::update_email(&client, &user.id, "willy_nilly")
+    let new_email = format!("willy_nilly_{}@test.org", DieselUlid::generate());
+    User::update_email(&client, &user.id, &new_email)
         .await
         .unwrap();
 
@@ -145,7 +149,7 @@ async fn update_user_email_test() {
     if let Some(updated_user) = User::get(user.id, &client).await.unwrap() {
         assert_eq!(user.id, updated_user.id);
         assert_eq!(user.display_name, updated_user.display_name);
-        assert_eq!(updated_user.email, "willy_nilly");
+        assert_eq!(updated_user.email, new_email);
         assert_eq!(user.attributes, updated_user.attributes);
         assert_eq!(user.active, updated_user.active);
     } else {
@@ -153,6 +157,146 @@ async fn update_user_email_test() {
     }
 }
 
+#[tokio::test]
+async fn unique_user_email_test() {
+    let db = init::init_database().await;
+    let client = db.get_client().await.unwrap();
+
+    let email = format!("{}@Test.org", DieselUlid::generate());
+    let mut user = test_utils::new_user(vec![]);
+    user.email.clone_from(&email);
+    user.create(&client).await.unwrap();
+
+    // Emails are compared case-insensitive
+    let mut duplicate = test_utils::new_user(vec![]);
+    duplicate.email = email.to_uppercase();
+    let err = duplicate.create(&client).await.unwrap_err();
+    assert!(err.downcast_ref::<EmailInUseError>().is_some());
+    assert_eq!(
+        User::get_by_email(&client, &email.to_lowercase())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Updates are rejected as well
+    let mut other = test_utils::new_user(vec![]);
+    other.create(&client).await.unwrap();
+    let err = User::update_email(&client, &other.id, email.to_lowercase())
+        .await
+        .unwrap_err();
+    assert!(err.downcast_ref::<EmailInUseError>().is_some());
+
+    // Unset emails are not unique
+    let mut first = test_utils::new_user(vec![]);
+    first.email = "".to_string();
+    first.create(&client).await.unwrap();
+    let mut second = test_utils::new_user(vec![]);
+    second.email = "".to_string();
+    second.create(&client).await.unwrap();
+}
+
+#[tokio::test]
+async fn check_unique_emails_test() {
+    let db = init::init_database().await;
+    let client = db.get_client().await.unwrap();
+
+    // Users from before the unique email index, in a schema of their own
+    let search_path = format!(
+        "emails_{}",
+        DieselUlid::generate().to_string().to_lowercase()
+    );
+    client
+        .batch_execute(&format!(
+            "CREATE SCHEMA {search_path}; SET search_path TO {search_path};
+            CREATE TABLE users (id UUID PRIMARY KEY, email TEXT NOT NULL DEFAULT '');"
+        ))
+        .await
+        .unwrap();
+    let (first, second) = (DieselUlid::generate(), DieselUlid::generate());
+    for (id, email) in [
+        (first, "Jane@test.org"),
+        (second, "jane@test.org"),
+        (DieselUlid::generate(), ""),
+        (DieselUlid::generate(), ""),
+    ] {
+        client
+            .execute(
+                "INSERT INTO users (id, email) VALUES ($1, $2);",
+                &[&id, &email],
+            )
+            .await
+            .unwrap();
+    }
+
+    // The conflicting users are named
+    let err = check_unique_emails(&client).await.unwrap_err().to_string();
+    assert!(err.contains("jane@test.org"));
+    assert!(err.contains(&first.to_string()) && err.contains(&second.to_string()));
+
+    client
+        .execute(
+            "UPDATE users SET email = 'john@test.org' WHERE id = $1;",
+            &[&second],
+        )
+        .await
+        .unwrap();
+    check_unique_emails(&client).await.unwrap();
+
+    client
+        .batch_execute(&format!(
+            "RESET search_path; DROP SCHEMA {search_path} CASCADE;"
+        ))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn unique_oidc_mapping_test() {
+    let db = init::init_database().await;
+    let client = db.get_client().await.unwrap();
+
+    let mapping = OIDCMapping {
+        external_id: DieselUlid::generate().to_string(),
+        oidc_name: "http://localhost.test/unique".to_string(),
+    };
+    let mut user = test_utils::new_user(vec![]);
+    user.attributes.0.external_ids = vec![mapping.clone()];
+    user.create(&client).await.unwrap();
+    assert_eq!(
+        User::get_by_oidc_mapping(&client, &mapping)
+            .await
+            .unwrap()
+            .unwrap()
+            .id,
+        user.id
+    );
+
+    // The identity can not be mapped to a second user
+    let mut duplicate = test_utils::new_user(vec![]);
+    duplicate.attributes.0.external_ids = vec![mapping.clone()];
+    let err = duplicate.create(&client).await.unwrap_err();
+    assert!(err.downcast_ref::<OIDCMappingInUseError>().is_some());
+    let mut other = test_utils::new_user(vec![]);
+    other.create(&client).await.unwrap();
+    let err = User::add_oidc_mapping(&client, &other.id, &mapping)
+        .await
+        .unwrap_err();
+    assert!(err.downcast_ref::<OIDCMappingInUseError>().is_some());
+
+    // Deleted users release their identities
+    user.delete(&client).await.unwrap();
+    assert!(User::get_by_oidc_mapping(&client, &mapping)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(User::add_oidc_mapping(&client, &other.id, &mapping)
+        .await
+        .unwrap()
+        .is_some());
+}
+
 #[tokio::test]
 async fn update_user_admin_test() {
     let db = init::init_database().await;
@@ -164,7 +308,7 @@ async fn update_user_admin_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -209,7 +353,7 @@ async fn update_user_service_account_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -254,7 +398,7 @@ async fn delete_user_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -291,7 +435,7 @@ async fn add_permission_user_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -361,7 +505,7 @@ async fn remove_user_permission_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -456,7 +600,7 @@ async fn update_user_permission_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -521,7 +665,7 @@ async fn user_token_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
@@ -630,7 +774,7 @@ async fn user_status_test() {
         display_name: "aha".to_string(),
         first_name: "".to_string(),
         last_name: "".to_string(),
-        email: "aja".to_string(),
+        email: format!("{}@test.org", DieselUlid::generate()),
         attributes: Json(UserAttributes {
             global_admin: false,
             service_account: true,
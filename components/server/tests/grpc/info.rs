@@ -1,7 +1,9 @@
+use aruna_rust_api::api::storage::models::v2::ComponentStatus;
 use aruna_rust_api::api::storage::models::v2::PageRequest;
 use aruna_rust_api::api::storage::services::v2::storage_status_service_server::StorageStatusService;
 use aruna_rust_api::api::storage::services::v2::{
     GetAnnouncementRequest, GetAnnouncementsByTypeRequest, GetAnnouncementsRequest,
+    GetStorageStatusRequest, GetStorageVersionRequest,
 };
 use aruna_rust_api::api::storage::{
     models::v2::AnnouncementType,
@@ -489,3 +491,57 @@ async fn get_announcements_by_type() {
         assert_eq!(a.announcement_type(), AnnouncementType::Release)
     }
 }
+
+#[tokio::test]
+async fn get_storage_version() {
+    // Init StorageStatusService
+    let info_service = init_storage_status_service().await;
+
+    // Version is readable without token
+    let response = info_service
+        .get_storage_version(Request::new(GetStorageVersionRequest {}))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.location_version.len(), 1);
+    let component = &response.location_version[0].version[0];
+    assert_eq!(component.name, "server");
+    let version = component.version.as_ref().unwrap();
+    assert_eq!(version.version_string, env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        version.major,
+        env!("CARGO_PKG_VERSION_MAJOR").parse::<i32>().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn get_storage_status() {
+    // Init StorageStatusService
+    let info_service = init_storage_status_service().await;
+
+    // Status is readable without token
+    let response = info_service
+        .get_storage_status(Request::new(GetStorageStatusRequest {}))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Server reports itself as available
+    let server = response
+        .location_status
+        .iter()
+        .find(|location| location.location == "aruna")
+        .unwrap();
+    assert_eq!(server.component_status[0].name, "server");
+    assert_eq!(
+        server.component_status[0].status,
+        ComponentStatus::Available as i32
+    );
+
+    // Default dataproxy of the test setup is listed
+    assert!(response.location_status.iter().any(|location| location
+        .component_status
+        .iter()
+        .any(|component| component.name == "dataproxy")));
+}
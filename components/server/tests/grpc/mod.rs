@@ -4,6 +4,7 @@ mod dataset;
 mod endpoint;
 mod info;
 mod licenses;
+mod object;
 mod project;
 mod search;
 mod user;
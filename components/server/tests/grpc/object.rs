@@ -0,0 +1,220 @@
+use std::{net::SocketAddr, str::FromStr, sync::Arc};
+
+use aruna_rust_api::api::storage::{
+    models::v2::{DataClass, EndpointHostConfig},
+    services::v2::{
+        create_object_request::Parent, object_service_server::ObjectService,
+        project_service_server::ProjectService, AddTrustedEndpointsUserRequest,
+        CreateEndpointRequest, CreateObjectRequest, CreateProjectRequest, GetDownloadUrlRequest,
+    },
+};
+use aruna_server::{
+    database::{
+        dsls::{license_dsl::ALL_RIGHTS_RESERVED, object_dsl::EndpointInfo},
+        enums::{ObjectStatus, ReplicationStatus, ReplicationType},
+    },
+    middlelayer::endpoints_request_types::CreateEP,
+    utils::rate_limiter::RateLimiter,
+};
+use diesel_ulid::DieselUlid;
+use tonic::{Code, Request};
+
+use crate::common::{
+    endpoint_mock,
+    init::{init_object_service_manual, init_service_block},
+    test_utils::{
+        add_token, fast_track_grpc_project_create, rand_string, ADMIN_OIDC_TOKEN, ADMIN_USER_ULID,
+        USER1_OIDC_TOKEN,
+    },
+};
+
+#[tokio::test]
+async fn grpc_get_download_url() {
+    // Init gRPC services
+    let service_block = init_service_block().await;
+
+    // Create endpoint with gRPC and S3 host and start its mock
+    let (endpoint, _pk) = service_block
+        .db_handler
+        .create_endpoint(CreateEP(CreateEndpointRequest {
+            name: "download_url_test_endpoint".to_string(),
+            ep_variant: 1,
+            is_public: true,
+            pubkey: "MCowBQYDK2VwAyEAyXJId1kDY2VRSlrR21qouVjtP//7celvJznH+Jd5MOw=".to_string(),
+            host_configs: vec![
+                EndpointHostConfig {
+                    url: "http://localhost:50096".to_string(),
+                    is_primary: true,
+                    ssl: false,
+                    public: true,
+                    host_variant: 1,
+                },
+                EndpointHostConfig {
+                    url: "localhost:50095".to_string(),
+                    is_primary: true,
+                    ssl: false,
+                    public: true,
+                    host_variant: 2,
+                },
+            ],
+        }))
+        .await
+        .unwrap();
+    let endpoint_task = endpoint_mock::start_server(
+        service_block.nats_handler.clone(),
+        "0.0.0.0:50096".parse::<SocketAddr>().unwrap(),
+        endpoint.id.to_string(),
+    )
+    .await
+    .unwrap();
+
+    // Create project on the endpoint and an object inside
+    let project = service_block
+        .project_service
+        .create_project(add_token(
+            Request::new(CreateProjectRequest {
+                name: rand_string(32).to_lowercase(),
+                title: "".to_string(),
+                description: "".to_string(),
+                key_values: vec![],
+                relations: vec![],
+                data_class: DataClass::Private as i32,
+                preferred_endpoint: endpoint.id.to_string(),
+                default_data_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+                metadata_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+                authors: vec![],
+            }),
+            ADMIN_OIDC_TOKEN,
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .project
+        .unwrap();
+    let object = service_block
+        .object_service
+        .create_object(add_token(
+            Request::new(CreateObjectRequest {
+                name: rand_string(32),
+                title: "".to_string(),
+                description: "".to_string(),
+                key_values: vec![],
+                relations: vec![],
+                data_class: DataClass::Private as i32,
+                hashes: vec![],
+                metadata_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+                data_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+                parent: Some(Parent::ProjectId(project.id.to_string())),
+                authors: vec![],
+            }),
+            ADMIN_OIDC_TOKEN,
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .object
+        .unwrap();
+    let object_ulid = DieselUlid::from_str(&object.id).unwrap();
+
+    // Initializing objects have no data to download
+    let request = GetDownloadUrlRequest {
+        object_id: object.id.to_string(),
+    };
+    let status = service_block
+        .object_service
+        .get_download_url(add_token(Request::new(request.clone()), ADMIN_OIDC_TOKEN))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    // Make object available on the endpoint and trust the endpoint
+    let mut cached = service_block.cache.get_object(&object_ulid).unwrap();
+    cached.object.object_status = ObjectStatus::AVAILABLE;
+    cached.object.endpoints.0.insert(
+        endpoint.id,
+        EndpointInfo {
+            replication: ReplicationType::FullSync,
+            status: Some(ReplicationStatus::Finished),
+        },
+    );
+    service_block.cache.upsert_object(&object_ulid, cached);
+    service_block
+        .db_handler
+        .add_trusted_endpoint_to_user(
+            DieselUlid::from_str(ADMIN_USER_ULID).unwrap(),
+            AddTrustedEndpointsUserRequest {
+                endpoint_id: endpoint.id.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // Available objects get a presigned url
+    let url = service_block
+        .object_service
+        .get_download_url(add_token(Request::new(request), ADMIN_OIDC_TOKEN))
+        .await
+        .unwrap()
+        .into_inner()
+        .url;
+    assert!(url.contains(&object.name));
+
+    endpoint_task.abort();
+}
+
+#[tokio::test]
+async fn grpc_get_download_url_rate_limit() {
+    // Init gRPC services with an url limiter that allows a single request
+    let service_block = init_service_block().await;
+    let object_service = init_object_service_manual(
+        service_block.db_handler.clone(),
+        service_block.auth_handler.clone(),
+        service_block.cache.clone(),
+        service_block.search_handler.clone(),
+        Some(Arc::new(RateLimiter::new(0.001, 1).unwrap())),
+    )
+    .await;
+
+    // Create project with an object inside
+    let project =
+        fast_track_grpc_project_create(&service_block.project_service, USER1_OIDC_TOKEN).await;
+    let object = object_service
+        .create_object(add_token(
+            Request::new(CreateObjectRequest {
+                name: rand_string(32),
+                title: "".to_string(),
+                description: "".to_string(),
+                key_values: vec![],
+                relations: vec![],
+                data_class: DataClass::Private as i32,
+                hashes: vec![],
+                metadata_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+                data_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+                parent: Some(Parent::ProjectId(project.id.to_string())),
+                authors: vec![],
+            }),
+            USER1_OIDC_TOKEN,
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .object
+        .unwrap();
+
+    // First request passes the limiter and fails on the object status
+    let request = GetDownloadUrlRequest {
+        object_id: object.id.to_string(),
+    };
+    let status = object_service
+        .get_download_url(add_token(Request::new(request.clone()), USER1_OIDC_TOKEN))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    // Second request exceeds the burst
+    let status = object_service
+        .get_download_url(add_token(Request::new(request), USER1_OIDC_TOKEN))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+}
@@ -1,13 +1,14 @@
 use std::str::FromStr;
 
 use aruna_rust_api::api::storage::{
-    models::v2::DataClass,
+    models::v2::{DataClass, Hash},
     services::v2::{
-        collection_service_server::CollectionService, project_service_server::ProjectService,
-        search_service_server::SearchService, user_service_server::UserService,
-        CreateCollectionRequest, CreateProjectRequest, GetPersonalNotificationsRequest,
-        GetResourceRequest, GetResourcesRequest, PersonalNotificationVariant, Reference,
-        ReferenceType, RequestResourceAccessRequest,
+        collection_service_server::CollectionService, object_service_server::ObjectService,
+        project_service_server::ProjectService, search_service_server::SearchService,
+        user_service_server::UserService, CreateCollectionRequest, CreateObjectRequest,
+        CreateProjectRequest, GetPersonalNotificationsRequest, GetResourceRequest,
+        GetResourcesRequest, PersonalNotificationVariant, Reference, ReferenceType,
+        RequestResourceAccessRequest,
     },
 };
 use aruna_server::database::{dsls::license_dsl::ALL_RIGHTS_RESERVED, enums::ObjectType};
@@ -424,3 +425,70 @@ async fn get_resource() {
     assert!(!confidential_collection.endpoints.is_empty());
     assert_eq!(confidential_collection.created_by, USER1_ULID);
 }
+
+#[tokio::test]
+async fn get_resource_redacts_private_object() {
+    // ------------------- INIT -----------------------
+    let service_block = init_service_block().await;
+
+    // Create private object with hashes
+    let project =
+        fast_track_grpc_project_create(&service_block.project_service, USER1_OIDC_TOKEN).await;
+    let create_request = CreateObjectRequest {
+        name: rand_string(32),
+        title: "".to_string(),
+        description: "A private object".to_string(),
+        key_values: vec![],
+        relations: vec![],
+        data_class: DataClass::Private as i32,
+        hashes: vec![Hash {
+            alg: 1,
+            hash: "dd98d701915b2bc5aad5dc9190194844".to_string(),
+        }],
+        metadata_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+        data_license_tag: ALL_RIGHTS_RESERVED.to_string(),
+        parent: Some(
+            aruna_rust_api::api::storage::services::v2::create_object_request::Parent::ProjectId(
+                project.id.to_string(),
+            ),
+        ),
+        authors: vec![],
+    };
+    let private_object = service_block
+        .object_service
+        .create_object(add_token(Request::new(create_request), USER1_OIDC_TOKEN))
+        .await
+        .unwrap()
+        .into_inner()
+        .object
+        .unwrap();
+    assert!(!private_object.hashes.is_empty());
+
+    // ------------------- TESTS ----------------------------------
+    // Anonymous readers get the metadata but no data related fields
+    let response = service_block
+        .search_service
+        .get_resource(Request::new(GetResourceRequest {
+            resource_id: private_object.id.to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .resource
+        .unwrap()
+        .resource
+        .unwrap()
+        .resource
+        .unwrap();
+    let object = match response {
+        aruna_rust_api::api::storage::models::v2::generic_resource::Resource::Object(object) => {
+            object
+        }
+        _ => panic!("This should be an object"),
+    };
+    assert_eq!(object.name, private_object.name);
+    assert_eq!(object.description, private_object.description);
+    assert!(object.hashes.is_empty());
+    assert!(object.endpoints.is_empty());
+    assert_eq!(object.created_by, DieselUlid::default().to_string());
+}
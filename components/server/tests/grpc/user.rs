@@ -189,7 +189,7 @@ async fn grpc_add_token() {
 
     // Get personal notifications of non-existing user
     let inner_request = CreateApiTokenRequest {
-        name: "my_second_token".to_string(),
+        name: format!("my_token_{}", DieselUlid::generate()),
         permission: None,
         expires_at: None,
     };
@@ -1,14 +1,22 @@
+use crate::common::endpoint_mock;
 use crate::common::init::init_database_handler_middlelayer;
+use aruna_rust_api::api::notification::services::v2::{
+    announcement_event::EventVariant as AnnouncementVariant, event_message::MessageVariant,
+};
 use aruna_rust_api::api::storage::services::v2::{
     CreateEndpointRequest, DeleteEndpointRequest, GetEndpointRequest,
 };
 use aruna_server::database::crud::CrudDb;
-use aruna_server::database::dsls::endpoint_dsl::{Endpoint, HostConfigs};
+use aruna_server::database::dsls::endpoint_dsl::{Endpoint, HostConfig, HostConfigs};
 use aruna_server::database::dsls::pub_key_dsl::PubKey;
-use aruna_server::database::enums::{EndpointStatus, EndpointVariant};
+use aruna_server::database::enums::{DataProxyFeature, EndpointStatus, EndpointVariant};
 use aruna_server::middlelayer::endpoints_request_types::{CreateEP, DeleteEP, GetEP};
+use aruna_server::notification::handler::EventStreamHandler;
+use aruna_server::notification::natsio_handler::NatsIOEventStreamHandler;
+use async_nats::jetstream::consumer::DeliverPolicy;
 use diesel_ulid::DieselUlid;
 use postgres_types::Json;
+use std::net::SocketAddr;
 
 #[tokio::test]
 async fn test_create_ep() {
@@ -177,3 +185,129 @@ async fn test_get_default_ep() {
     };
     endpoint.create(&client).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_pubkey_announcements() {
+    // init
+    let db_handler = init_database_handler_middlelayer().await;
+    let announcement_consumer = db_handler
+        .natsio_handler
+        .create_internal_consumer(
+            DieselUlid::generate(),
+            "AOS.ANNOUNCEMENT.PUBKEY.>".to_string(),
+            DeliverPolicy::New,
+            true,
+        )
+        .await
+        .unwrap();
+    let stream_handler = NatsIOEventStreamHandler::new(announcement_consumer);
+
+    // Creating an endpoint announces its pubkey exactly once
+    let request = CreateEP(CreateEndpointRequest {
+        name: "pubkey_announcement_test".to_string(),
+        ep_variant: 1,
+        is_public: true,
+        pubkey: "MCowBQYDK2VwAyEApRYFXvzOWW7tk7CR6Xxj9NJDYwnjlVUBkJZrVN53dl4=".to_string(),
+        host_configs: vec![],
+    });
+    let (ep, pk) = db_handler.create_endpoint(request).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let announcements = pubkey_announcements(&stream_handler).await;
+    assert_eq!(
+        announcements
+            .iter()
+            .filter(|ann| **ann == AnnouncementVariant::NewPubkey(pk.id as i32))
+            .count(),
+        1
+    );
+
+    // Deleting the endpoint removes and announces its pubkey exactly once
+    let request = DeleteEP(DeleteEndpointRequest {
+        endpoint_id: ep.id.to_string(),
+    });
+    db_handler.delete_endpoint(request).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let announcements = pubkey_announcements(&stream_handler).await;
+    assert_eq!(
+        announcements
+            .iter()
+            .filter(|ann| **ann == AnnouncementVariant::RemovePubkey(pk.id as i32))
+            .count(),
+        1
+    );
+    assert!(db_handler.cache.get_pubkey(pk.id).is_none());
+}
+
+async fn pubkey_announcements(
+    stream_handler: &NatsIOEventStreamHandler,
+) -> Vec<AnnouncementVariant> {
+    stream_handler
+        .get_event_consumer_messages(100)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(
+            |msg| match serde_json::from_slice::<MessageVariant>(&msg.payload).unwrap() {
+                MessageVariant::AnnouncementEvent(event) => event.event_variant,
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+#[tokio::test]
+async fn test_endpoint_health_status() {
+    // init
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let mut endpoint = Endpoint {
+        id: DieselUlid::generate(),
+        name: "health_test".to_string(),
+        host_config: Json(HostConfigs(vec![HostConfig {
+            url: "http://localhost:50097".to_string(),
+            is_primary: true,
+            ssl: false,
+            public: true,
+            feature: DataProxyFeature::GRPC,
+        }])),
+        endpoint_variant: EndpointVariant::PERSISTENT,
+        documentation_object: None,
+        is_public: false,
+        status: EndpointStatus::INITIALIZING,
+    };
+    endpoint.create(&client).await.unwrap();
+    let endpoint_id = endpoint.id.to_string();
+    let get_request = || {
+        GetEP(GetEndpointRequest {
+            endpoint: Some(
+                aruna_rust_api::api::storage::services::v2::get_endpoint_request::Endpoint::EndpointId(
+                    endpoint_id.clone(),
+                ),
+            ),
+        })
+    };
+
+    // Reachable endpoint becomes available
+    let task = endpoint_mock::start_server(
+        db_handler.natsio_handler.clone(),
+        "0.0.0.0:50097".parse::<SocketAddr>().unwrap(),
+        endpoint.id.to_string(),
+    )
+    .await
+    .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    endpoint.refresh_status(&client).await.unwrap();
+    let fetched = db_handler.get_endpoint(get_request()).await.unwrap();
+    assert_eq!(fetched.status, EndpointStatus::AVAILABLE);
+
+    // Stopped endpoint becomes unavailable
+    task.abort();
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    endpoint.refresh_status(&client).await.unwrap();
+    let fetched = db_handler.get_endpoint(get_request()).await.unwrap();
+    assert_eq!(fetched.status, EndpointStatus::UNAVAILABLE);
+
+    endpoint.delete(&client).await.unwrap();
+}
@@ -4,7 +4,9 @@ mod delete;
 mod endpoints;
 mod licenses;
 mod relations;
+mod replication;
 mod rules;
+mod service_accounts;
 mod snapshots;
 mod updates;
 mod users;
@@ -333,3 +333,109 @@ async fn test_modify_relations_constraint() {
             .is_empty()
     );
 }
+
+#[tokio::test]
+async fn test_move_resource() {
+    // init
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+
+    // create user and objects
+    let project_id = DieselUlid::generate();
+    let old_collection_id = DieselUlid::generate();
+    let new_collection_id = DieselUlid::generate();
+    let object_id = DieselUlid::generate();
+    let mut user = test_utils::new_user(vec![
+        ObjectMapping::PROJECT(project_id),
+        ObjectMapping::COLLECTION(old_collection_id),
+        ObjectMapping::COLLECTION(new_collection_id),
+        ObjectMapping::OBJECT(object_id),
+    ]);
+    user.create(&client).await.unwrap();
+    let objects = vec![
+        test_utils::new_object(user.id, project_id, ObjectType::PROJECT),
+        test_utils::new_object(user.id, old_collection_id, ObjectType::COLLECTION),
+        test_utils::new_object(user.id, new_collection_id, ObjectType::COLLECTION),
+        test_utils::new_object(user.id, object_id, ObjectType::OBJECT),
+    ];
+    Object::batch_create(&objects, &client).await.unwrap();
+    let relations = vec![
+        test_utils::new_internal_relation(&objects[0], &objects[1]),
+        test_utils::new_internal_relation(&objects[0], &objects[2]),
+        test_utils::new_internal_relation(&objects[1], &objects[3]),
+    ];
+    InternalRelation::batch_create(&relations, &client)
+        .await
+        .unwrap();
+    for owr in Object::get_objects_with_relations(&objects.iter().map(|o| o.id).collect(), &client)
+        .await
+        .unwrap()
+    {
+        db_handler.cache.upsert_object(&owr.object.id, owr.clone());
+    }
+
+    let belongs_to = |resource_id: DieselUlid,
+                      resource_variant: ResourceVariant,
+                      direction: RelationDirection| Relation {
+        relation: Some(RelationEnum::Internal(APIInternalRelation {
+            resource_id: resource_id.to_string(),
+            defined_variant: InternalRelationVariant::BelongsTo as i32,
+            custom_variant: None,
+            resource_variant: resource_variant as i32,
+            direction: direction as i32,
+        })),
+    };
+
+    // 1. object can be moved from one collection to another in one request
+    let request = ModifyRelations(ModifyRelationsRequest {
+        resource_id: object_id.to_string(),
+        add_relations: vec![belongs_to(
+            new_collection_id,
+            ResourceVariant::Collection,
+            RelationDirection::Inbound,
+        )],
+        remove_relations: vec![belongs_to(
+            old_collection_id,
+            ResourceVariant::Collection,
+            RelationDirection::Inbound,
+        )],
+    });
+    let (obj, mod_lab) = db_handler.get_resource(request).await.unwrap();
+    let owr = db_handler
+        .modify_relations(obj, mod_lab.relations_to_add, mod_lab.relations_to_remove)
+        .await
+        .unwrap();
+    assert_eq!(owr.inbound_belongs_to.0.len(), 1);
+    assert!(owr.inbound_belongs_to.0.contains_key(&new_collection_id));
+    assert!(db_handler
+        .cache
+        .get_object(&old_collection_id)
+        .unwrap()
+        .outbound_belongs_to
+        .0
+        .is_empty());
+
+    // 2. an object can not become the parent of a collection
+    let request = ModifyRelations(ModifyRelationsRequest {
+        resource_id: new_collection_id.to_string(),
+        add_relations: vec![belongs_to(
+            object_id,
+            ResourceVariant::Object,
+            RelationDirection::Inbound,
+        )],
+        remove_relations: vec![],
+    });
+    assert!(db_handler.get_resource(request).await.is_err());
+
+    // 3. a project can not be moved below its own collection (cycle)
+    let request = ModifyRelations(ModifyRelationsRequest {
+        resource_id: new_collection_id.to_string(),
+        add_relations: vec![belongs_to(
+            project_id,
+            ResourceVariant::Project,
+            RelationDirection::Outbound,
+        )],
+        remove_relations: vec![],
+    });
+    assert!(db_handler.get_resource(request).await.is_err());
+}
@@ -0,0 +1,48 @@
+use crate::common::init::init_database_handler_middlelayer;
+use crate::common::test_utils;
+use aruna_rust_api::api::storage::models::v2::ReplicationStatus as APIReplicationStatus;
+use aruna_rust_api::api::storage::services::v2::UpdateReplicationStatusRequest;
+use aruna_server::database::crud::CrudDb;
+use aruna_server::database::dsls::object_dsl::Object;
+use aruna_server::database::enums::{ObjectType, ReplicationStatus};
+use diesel_ulid::DieselUlid;
+
+#[tokio::test]
+async fn test_update_replication_status_transitions() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let mut user = test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+
+    // Object starts with a waiting data location
+    let mut object = test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::OBJECT);
+    object.create(&client).await.unwrap();
+    let endpoint_id = *object.endpoints.0.iter().next().unwrap().key();
+    let request = |status: APIReplicationStatus| UpdateReplicationStatusRequest {
+        object_id: object.id.to_string(),
+        endpoint_id: endpoint_id.to_string(),
+        status: status as i32,
+    };
+
+    // Waiting -> Running is legal
+    db_handler
+        .update_replication_status(request(APIReplicationStatus::Running))
+        .await
+        .unwrap();
+    let updated = Object::get(object.id, &client).await.unwrap().unwrap();
+    assert_eq!(
+        updated.endpoints.0.get(&endpoint_id).unwrap().status,
+        Some(ReplicationStatus::Running)
+    );
+
+    // Running -> Waiting is rejected and leaves the status untouched
+    assert!(db_handler
+        .update_replication_status(request(APIReplicationStatus::Waiting))
+        .await
+        .is_err());
+    let unchanged = Object::get(object.id, &client).await.unwrap().unwrap();
+    assert_eq!(
+        unchanged.endpoints.0.get(&endpoint_id).unwrap().status,
+        Some(ReplicationStatus::Running)
+    );
+}
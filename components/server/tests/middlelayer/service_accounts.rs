@@ -0,0 +1,88 @@
+use crate::common::init::{
+    init_database_handler_middlelayer, init_permission_handler, init_token_handler,
+};
+use crate::common::test_utils;
+use aruna_rust_api::api::storage::models::v2::permission::ResourceId;
+use aruna_rust_api::api::storage::models::v2::{Permission, PermissionLevel};
+use aruna_rust_api::api::storage::services::v2::{
+    CreateServiceAccountRequest, CreateServiceAccountTokenRequest,
+};
+use aruna_server::auth::structs::Context;
+use aruna_server::database::crud::CrudDb;
+use aruna_server::database::dsls::object_dsl::Object;
+use aruna_server::database::dsls::user_dsl::User;
+use aruna_server::database::enums::{DbPermissionLevel, ObjectType};
+use aruna_server::middlelayer::service_account_request_types::{
+    CreateServiceAccount, CreateServiceAccountToken,
+};
+use diesel_ulid::DieselUlid;
+
+#[tokio::test]
+async fn test_create_service_account_with_token() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let token_handler =
+        init_token_handler(db_handler.database.clone(), db_handler.cache.clone()).await;
+    let authorizer = init_permission_handler(db_handler.cache.clone(), token_handler).await;
+
+    // Create project
+    let mut user = test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+    let mut project = test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::PROJECT);
+    project.create(&client).await.unwrap();
+    let project = Object::get_object_with_relations(&project.id, &client)
+        .await
+        .unwrap();
+    db_handler.cache.add_object(project.clone());
+    let project_id = project.object.id;
+
+    // Create service account
+    let service_account = db_handler
+        .create_service_account(CreateServiceAccount(CreateServiceAccountRequest {
+            name: "test_svc_account".to_string(),
+            project_id: project_id.to_string(),
+            permission_level: PermissionLevel::Read as i32,
+        }))
+        .await
+        .unwrap();
+    assert!(service_account.attributes.0.service_account);
+
+    // Issue token for service account
+    let (token, secret) = db_handler
+        .create_service_account_token(
+            authorizer.clone(),
+            CreateServiceAccountToken(CreateServiceAccountTokenRequest {
+                svc_account_id: service_account.id.to_string(),
+                permission: Some(Permission {
+                    permission_level: PermissionLevel::Read as i32,
+                    resource_id: Some(ResourceId::ProjectId(project_id.to_string())),
+                }),
+                name: "test_svc_token".to_string(),
+                expires_at: None,
+            }),
+        )
+        .await
+        .unwrap();
+    let token = token.unwrap();
+
+    // Token is persisted on the service account itself
+    let db_account = User::get(service_account.id, &client)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(db_account
+        .attributes
+        .0
+        .tokens
+        .contains_key(&token.id.parse::<DieselUlid>().unwrap()));
+
+    // Token authorizes on the associated project
+    let user_id = authorizer
+        .check_permissions(
+            &secret,
+            vec![Context::res_ctx(project_id, DbPermissionLevel::READ, true)],
+        )
+        .await
+        .unwrap();
+    assert_eq!(user_id, service_account.id);
+}
@@ -1,19 +1,21 @@
 use crate::common::init::init_database_handler_middlelayer;
 use crate::common::test_utils;
-use aruna_rust_api::api::storage::models::v2::{Hash, KeyValue as APIKeyValue};
+use aruna_rust_api::api::storage::models::v2::{
+    Author as APIAuthor, Hash, KeyValue as APIKeyValue,
+};
 use aruna_rust_api::api::storage::services::v2::{
     UpdateCollectionDataClassRequest, UpdateCollectionDescriptionRequest,
     UpdateCollectionKeyValuesRequest, UpdateCollectionNameRequest, UpdateDatasetDataClassRequest,
     UpdateDatasetDescriptionRequest, UpdateDatasetKeyValuesRequest, UpdateDatasetNameRequest,
-    UpdateObjectRequest, UpdateProjectDataClassRequest, UpdateProjectDescriptionRequest,
-    UpdateProjectKeyValuesRequest, UpdateProjectNameRequest,
+    UpdateObjectRequest, UpdateProjectAuthorsRequest, UpdateProjectDataClassRequest,
+    UpdateProjectDescriptionRequest, UpdateProjectKeyValuesRequest, UpdateProjectNameRequest,
 };
 use aruna_server::database::crud::CrudDb;
 use aruna_server::database::dsls::license_dsl::ALL_RIGHTS_RESERVED;
 use aruna_server::database::dsls::object_dsl::{KeyValue, KeyValueVariant, KeyValues, Object};
 use aruna_server::database::enums::{DataClass, ObjectMapping, ObjectStatus, ObjectType};
 use aruna_server::middlelayer::update_request_types::{
-    DataClassUpdate, DescriptionUpdate, KeyValueUpdate, NameUpdate,
+    DataClassUpdate, DescriptionUpdate, KeyValueUpdate, NameUpdate, UpdateAuthor, UpdateObject,
 };
 use diesel_ulid::DieselUlid;
 use itertools::Itertools;
@@ -296,7 +298,7 @@ async fn test_update_keyvals() {
                     add_key_values: vec![valid.clone(), static_kv.clone()],
                     remove_key_values: vec![deleted.clone()],
                 });
-                db_handler.update_keyvals(request).await.unwrap();
+                db_handler.update_keyvals(request, false).await.unwrap();
                 assert!(Object::get(r.id, &client)
                     .await
                     .unwrap()
@@ -318,7 +320,25 @@ async fn test_update_keyvals() {
                     add_key_values: vec![],
                     remove_key_values: vec![static_kv.clone()],
                 });
-                assert!(db_handler.update_keyvals(err).await.is_err());
+                assert!(db_handler.update_keyvals(err, false).await.is_err());
+                // Admins can remove static labels
+                let admin_request = KeyValueUpdate::Project(UpdateProjectKeyValuesRequest {
+                    project_id: r.id.to_string(),
+                    add_key_values: vec![],
+                    remove_key_values: vec![static_kv.clone()],
+                });
+                db_handler
+                    .update_keyvals(admin_request, true)
+                    .await
+                    .unwrap();
+                assert!(!Object::get(r.id, &client)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .key_values
+                    .0
+                     .0
+                    .contains(&static_converted));
             }
             ObjectType::COLLECTION => {
                 let request = KeyValueUpdate::Collection(UpdateCollectionKeyValuesRequest {
@@ -326,7 +346,7 @@ async fn test_update_keyvals() {
                     add_key_values: vec![valid.clone(), static_kv.clone()],
                     remove_key_values: vec![deleted.clone()],
                 });
-                db_handler.update_keyvals(request).await.unwrap();
+                db_handler.update_keyvals(request, false).await.unwrap();
                 assert!(Object::get(r.id, &client)
                     .await
                     .unwrap()
@@ -348,7 +368,7 @@ async fn test_update_keyvals() {
                     add_key_values: vec![],
                     remove_key_values: vec![static_kv.clone()],
                 });
-                assert!(db_handler.update_keyvals(err).await.is_err());
+                assert!(db_handler.update_keyvals(err, false).await.is_err());
             }
             ObjectType::DATASET => {
                 let request = KeyValueUpdate::Dataset(UpdateDatasetKeyValuesRequest {
@@ -356,7 +376,7 @@ async fn test_update_keyvals() {
                     add_key_values: vec![valid.clone(), static_kv.clone()],
                     remove_key_values: vec![deleted.clone()],
                 });
-                db_handler.update_keyvals(request).await.unwrap();
+                db_handler.update_keyvals(request, false).await.unwrap();
                 assert!(Object::get(r.id, &client)
                     .await
                     .unwrap()
@@ -378,7 +398,7 @@ async fn test_update_keyvals() {
                     add_key_values: vec![],
                     remove_key_values: vec![static_kv.clone()],
                 });
-                assert!(db_handler.update_keyvals(err).await.is_err());
+                assert!(db_handler.update_keyvals(err, false).await.is_err());
             }
             _ => panic!(),
         };
@@ -437,7 +457,7 @@ async fn update_object_test() {
 
     // Test in place update
     let (updated, is_new) = db_handler
-        .update_grpc_object(update_request, user.id, false)
+        .update_grpc_object(update_request, user.id, false, false)
         .await
         .unwrap();
     assert!(!is_new);
@@ -473,7 +493,7 @@ async fn update_object_test() {
 
     // test new revision update
     let (new, is_new) = db_handler
-        .update_grpc_object(trigger_new_request, user.id, false)
+        .update_grpc_object(trigger_new_request, user.id, false, false)
         .await
         .unwrap();
     assert!(is_new);
@@ -502,7 +522,7 @@ async fn update_object_test() {
     };
 
     let (new_2, is_new_2) = db_handler
-        .update_grpc_object(force_new_revision, user.id, false)
+        .update_grpc_object(force_new_revision, user.id, false, false)
         .await
         .unwrap();
     assert!(is_new_2);
@@ -524,7 +544,7 @@ async fn update_object_test() {
         data_license_tag: Some(ALL_RIGHTS_RESERVED.to_string()),
     };
     let (license_updated, is_new) = db_handler
-        .update_grpc_object(license_update.clone(), user.id, false)
+        .update_grpc_object(license_update.clone(), user.id, false, false)
         .await
         .unwrap();
     assert!(is_new);
@@ -537,3 +557,164 @@ async fn update_object_test() {
         Some(license_updated.object.data_license)
     )
 }
+
+#[tokio::test]
+async fn test_update_object_static_labels() {
+    // Init
+    let db_handler = init_database_handler_middlelayer().await;
+    let object_id = DieselUlid::generate();
+    let object_mapping = ObjectMapping::OBJECT(object_id);
+    let mut user = test_utils::new_user(vec![object_mapping]);
+    let mut object = test_utils::object_from_mapping(user.id, object_mapping);
+    let static_label = KeyValue {
+        key: "static".to_string(),
+        value: "label".to_string(),
+        variant: KeyValueVariant::STATIC_LABEL,
+    };
+    object.key_values.0 .0.push(static_label.clone());
+    let client = db_handler.database.get_client().await.unwrap();
+    user.create(&client).await.unwrap();
+    object.create(&client).await.unwrap();
+    let owr = Object::get_object_with_relations(&object_id, &client)
+        .await
+        .unwrap();
+    db_handler.cache.add_object(owr);
+
+    let remove_request = UpdateObjectRequest {
+        object_id: object_id.to_string(),
+        name: None,
+        description: None,
+        add_key_values: vec![],
+        remove_key_values: vec![APIKeyValue {
+            key: "static".to_string(),
+            value: "label".to_string(),
+            variant: 2,
+        }],
+        data_class: 0,
+        hashes: vec![],
+        parent: None,
+        force_revision: false,
+        data_license_tag: None,
+        metadata_license_tag: None,
+    };
+
+    // Only admins can remove static labels
+    assert!(db_handler
+        .update_grpc_object(remove_request.clone(), user.id, false, false)
+        .await
+        .is_err());
+    assert!(Object::get(object_id, &client)
+        .await
+        .unwrap()
+        .unwrap()
+        .key_values
+        .0
+         .0
+        .contains(&static_label));
+    let (updated, is_new) = db_handler
+        .update_grpc_object(remove_request, user.id, false, true)
+        .await
+        .unwrap();
+    assert!(is_new);
+    assert!(!updated.object.key_values.0 .0.contains(&static_label));
+}
+
+#[tokio::test]
+async fn test_update_authors() {
+    // Init
+    let db_handler = init_database_handler_middlelayer().await;
+    let project_id = DieselUlid::generate();
+    let mut user = test_utils::new_user(vec![ObjectMapping::PROJECT(project_id)]);
+    let mut project = test_utils::object_from_mapping(user.id, ObjectMapping::PROJECT(project_id));
+    let client = db_handler.database.get_client().await.unwrap();
+    user.create(&client).await.unwrap();
+    project.create(&client).await.unwrap();
+    let author = APIAuthor {
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        email: Some("john.doe@test.org".to_string()),
+        orcid: None,
+        id: Some(user.id.to_string()),
+    };
+    let update = |add_authors, remove_authors| {
+        UpdateAuthor::Project(UpdateProjectAuthorsRequest {
+            project_id: project_id.to_string(),
+            add_authors,
+            remove_authors,
+        })
+    };
+
+    // Add author
+    let updated = db_handler
+        .update_author(update(vec![author.clone()], vec![]))
+        .await
+        .unwrap();
+    assert_eq!(updated.object.authors.0.len(), 2);
+
+    // Adding the same user again is deduplicated
+    let renamed = APIAuthor {
+        first_name: "Johnny".to_string(),
+        ..author.clone()
+    };
+    let updated = db_handler
+        .update_author(update(vec![author.clone(), renamed], vec![]))
+        .await
+        .unwrap();
+    assert_eq!(updated.object.authors.0.len(), 2);
+
+    // Invalid emails are rejected
+    let invalid = APIAuthor {
+        email: Some("not-an-email".to_string()),
+        ..author.clone()
+    };
+    assert!(db_handler
+        .update_author(update(vec![invalid], vec![]))
+        .await
+        .is_err());
+
+    // Remove author
+    let updated = db_handler
+        .update_author(update(vec![], vec![author]))
+        .await
+        .unwrap();
+    assert_eq!(updated.object.authors.0.len(), 1);
+    assert!(updated.object.authors.0.iter().all(|a| a.user_id.is_none()));
+}
+
+#[test]
+fn test_service_account_dataclass() {
+    let mut old = test_utils::new_object(
+        DieselUlid::generate(),
+        DieselUlid::generate(),
+        ObjectType::OBJECT,
+    );
+    old.data_class = DataClass::WORKSPACE;
+    let update = |data_class: DataClass| {
+        UpdateObject(UpdateObjectRequest {
+            object_id: old.id.to_string(),
+            data_class: data_class as i32,
+            ..Default::default()
+        })
+    };
+
+    // Service accounts may keep objects in their workspace ...
+    let (data_class, _) = update(DataClass::WORKSPACE)
+        .get_dataclass(old.clone(), true)
+        .unwrap();
+    assert_eq!(data_class, DataClass::WORKSPACE);
+    let unspecified = UpdateObject(UpdateObjectRequest {
+        object_id: old.id.to_string(),
+        ..Default::default()
+    });
+    let (data_class, _) = unspecified.get_dataclass(old.clone(), true).unwrap();
+    assert_eq!(data_class, DataClass::WORKSPACE);
+
+    // ... but need a claimed workspace for any other dataclass
+    assert!(update(DataClass::PRIVATE)
+        .get_dataclass(old.clone(), true)
+        .is_err());
+    let (data_class, _) = update(DataClass::PRIVATE)
+        .get_dataclass(old.clone(), false)
+        .unwrap();
+    assert_eq!(data_class, DataClass::PRIVATE);
+}
@@ -1,14 +1,26 @@
 use crate::common::init::init_database_handler_middlelayer;
 use crate::common::test_utils;
+use aruna_rust_api::api::notification::services::v2::{
+    event_message::MessageVariant, EventVariant,
+};
 use aruna_rust_api::api::storage::services::v2::{
-    ActivateUserRequest, DeactivateUserRequest, UpdateUserDisplayNameRequest,
-    UpdateUserEmailRequest,
+    ActivateUserRequest, AddTrustedEndpointsUserRequest, CreateApiTokenRequest,
+    DeactivateUserRequest, DeleteApiTokenRequest, RegisterUserRequest,
+    RemoveTrustedEndpointsUserRequest, UpdateUserDisplayNameRequest, UpdateUserEmailRequest,
 };
 use aruna_server::database::crud::CrudDb;
-use aruna_server::database::dsls::user_dsl::User;
+use aruna_server::database::dsls::object_dsl::ObjectWithRelations;
+use aruna_server::database::dsls::user_dsl::{OIDCMapping, TokenNameInUseError, User};
+use aruna_server::database::enums::{DbPermissionLevel, ObjectMapping, ObjectType};
+use aruna_server::middlelayer::token_request_types::{CreateToken, DeleteToken};
 use aruna_server::middlelayer::user_request_types::{
-    ActivateUser, DeactivateUser, UpdateUserEmail, UpdateUserName,
+    ActivateUser, DeactivateUser, RegisterUser, UpdateUserEmail, UpdateUserName,
 };
+use aruna_server::notification::handler::{EventHandler, EventType};
+use async_nats::jetstream::consumer::DeliverPolicy;
+use dashmap::DashMap;
+use diesel_ulid::DieselUlid;
+use postgres_types::Json;
 
 /*
 #[tokio::test]
@@ -95,7 +107,7 @@ async fn test_update_email() {
     user.create(&client).await.unwrap();
 
     // Test email update
-    let new_email = "updated@test.org".to_string();
+    let new_email = format!("{}@test.org", test_utils::rand_string(16));
     let request = UpdateUserEmail(UpdateUserEmailRequest {
         user_id: user.id.to_string(),
         new_email: new_email.clone(),
@@ -105,3 +117,284 @@ async fn test_update_email() {
     let db_user = User::get(user.id, &client).await.unwrap().unwrap();
     assert_eq!(&db_user.email, &new_email);
 }
+
+#[tokio::test]
+async fn test_register_duplicate_email() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let email = format!("{}@test.org", test_utils::rand_string(16));
+    let register = |email: &str| {
+        RegisterUser(RegisterUserRequest {
+            display_name: "test_name".to_string(),
+            email: email.to_string(),
+            project: "".to_string(),
+        })
+    };
+    let mapping = || OIDCMapping {
+        external_id: DieselUlid::generate().to_string(),
+        oidc_name: "test".to_string(),
+    };
+
+    // First registration succeeds
+    let user = db_handler
+        .register_user(register(&email), mapping())
+        .await
+        .unwrap();
+    assert_eq!(user.email, email);
+
+    // Second registration with the same email is rejected
+    assert!(db_handler
+        .register_user(register(&email), mapping())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_update_to_duplicate_email() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let email = format!("{}@test.org", test_utils::rand_string(16));
+    let mut user = test_utils::new_user(vec![]);
+    user.email.clone_from(&email);
+    user.create(&client).await.unwrap();
+    let mut other = test_utils::new_user(vec![]);
+    other.create(&client).await.unwrap();
+
+    // Updating to an email already in use is rejected
+    let request = UpdateUserEmail(UpdateUserEmailRequest {
+        user_id: other.id.to_string(),
+        new_email: email.clone(),
+    });
+    assert!(db_handler.update_email(request, other.id).await.is_err());
+    let db_user = User::get(other.id, &client).await.unwrap().unwrap();
+    assert_ne!(db_user.email, email);
+
+    // Re-setting the own email is still allowed
+    let request = UpdateUserEmail(UpdateUserEmailRequest {
+        user_id: user.id.to_string(),
+        new_email: email.clone(),
+    });
+    assert!(db_handler.update_email(request, user.id).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_add_remove_oidc_provider() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let mapping = |oidc_name: &str| OIDCMapping {
+        external_id: DieselUlid::generate().to_string(),
+        oidc_name: oidc_name.to_string(),
+    };
+    let first = mapping("http://localhost.test/first");
+    let second = mapping("http://localhost.test/second");
+    let mut user = test_utils::new_user(vec![]);
+    user.attributes.0.external_ids = vec![first.clone()];
+    user.create(&client).await.unwrap();
+    db_handler.cache.add_user(user.id, user.clone());
+
+    // Add second provider
+    let user = db_handler
+        .add_oidc_provider(user.id, &second)
+        .await
+        .unwrap();
+    assert_eq!(
+        user.attributes.0.external_ids,
+        vec![first.clone(), second.clone()]
+    );
+
+    // Adding the same mapping again is rejected
+    assert!(db_handler
+        .add_oidc_provider(user.id, &second)
+        .await
+        .is_err());
+    let db_user = User::get(user.id, &client).await.unwrap().unwrap();
+    assert_eq!(db_user.attributes.0.external_ids.len(), 2);
+
+    // Remove first provider
+    let user = db_handler
+        .remove_oidc_provider(user.id, &first.oidc_name)
+        .await
+        .unwrap();
+    assert_eq!(user.attributes.0.external_ids, vec![second.clone()]);
+    assert!(db_handler.cache.get_user_by_oidc(&first).is_none());
+
+    // Removing the last provider is rejected
+    assert!(db_handler
+        .remove_oidc_provider(user.id, &second.oidc_name)
+        .await
+        .is_err());
+    let db_user = User::get(user.id, &client).await.unwrap().unwrap();
+    assert_eq!(db_user.attributes.0.external_ids, vec![second]);
+}
+
+#[tokio::test]
+async fn test_add_permission_emits_user_event() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let mut user = test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+    let mut project = test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::PROJECT);
+    project.create(&client).await.unwrap();
+
+    // Create consumer on the user subject before the permission change
+    let (consumer_id, _) = db_handler
+        .natsio_handler
+        .create_event_consumer(EventType::User(user.id.to_string()), DeliverPolicy::All)
+        .await
+        .unwrap();
+
+    // Grant permission on project
+    db_handler
+        .add_permission_to_user(
+            user.id,
+            project.id,
+            &project.name,
+            ObjectMapping::PROJECT(DbPermissionLevel::WRITE),
+            false,
+        )
+        .await
+        .unwrap();
+
+    // Give Nats time to process the published messages
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    // Validate exactly one user event landed on the user subject
+    let messages = db_handler
+        .natsio_handler
+        .get_event_consumer_messages(consumer_id.to_string(), 10)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+
+    let msg_variant: MessageVariant =
+        serde_json::from_slice(messages[0].message.payload.to_vec().as_slice()).unwrap();
+    match msg_variant {
+        MessageVariant::UserEvent(event) => {
+            assert_eq!(event.user_id, user.id.to_string());
+            assert_eq!(event.event_variant, EventVariant::Updated as i32);
+        }
+        _ => panic!("Expected user event"),
+    }
+
+    // Clean up consumer
+    db_handler
+        .natsio_handler
+        .delete_event_consumer(consumer_id.to_string())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_trusted_endpoint_lifecycle() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let mut user = test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+    db_handler.cache.add_user(user.id, user.clone());
+
+    // Object created by the user with a data location on the endpoint
+    let object = test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::OBJECT);
+    let endpoint_id = *object.endpoints.0.iter().next().unwrap().key();
+    db_handler.cache.add_object(ObjectWithRelations {
+        object: object.clone(),
+        inbound: Json(DashMap::default()),
+        inbound_belongs_to: Json(DashMap::default()),
+        outbound: Json(DashMap::default()),
+        outbound_belongs_to: Json(DashMap::default()),
+    });
+
+    // Add trusted endpoint and list it
+    let added = db_handler
+        .add_trusted_endpoint_to_user(
+            user.id,
+            AddTrustedEndpointsUserRequest {
+                endpoint_id: endpoint_id.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(added
+        .attributes
+        .0
+        .trusted_endpoints
+        .contains_key(&endpoint_id));
+    assert!(db_handler
+        .cache
+        .get_user(&user.id)
+        .unwrap()
+        .attributes
+        .0
+        .trusted_endpoints
+        .contains_key(&endpoint_id));
+
+    // Removal fails while the endpoint still holds user data
+    let request = RemoveTrustedEndpointsUserRequest {
+        endpoint_id: endpoint_id.to_string(),
+    };
+    assert!(db_handler
+        .remove_trusted_endpoint_from_user(user.id, request.clone())
+        .await
+        .is_err());
+
+    // Removal succeeds once the data is gone
+    db_handler.cache.remove_object(&object.id);
+    let removed = db_handler
+        .remove_trusted_endpoint_from_user(user.id, request)
+        .await
+        .unwrap();
+    assert!(!removed
+        .attributes
+        .0
+        .trusted_endpoints
+        .contains_key(&endpoint_id));
+}
+
+#[tokio::test]
+async fn test_unique_token_names() {
+    let db_handler = init_database_handler_middlelayer().await;
+    let client = db_handler.database.get_client().await.unwrap();
+    let mut user = test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+    let create = |name: &str| {
+        CreateToken(CreateApiTokenRequest {
+            name: name.to_string(),
+            permission: None,
+            expires_at: None,
+        })
+    };
+
+    let (token_id, _) = db_handler
+        .create_token(&user.id, 1, create("my_token"))
+        .await
+        .unwrap();
+
+    // The same name is rejected and not persisted
+    assert!(db_handler
+        .create_token(&user.id, 1, create("my_token"))
+        .await
+        .unwrap_err()
+        .downcast_ref::<TokenNameInUseError>()
+        .is_some());
+    let db_user = User::get(user.id, &client).await.unwrap().unwrap();
+    assert_eq!(db_user.count_tokens_named("my_token"), 1);
+
+    // Other names are still allowed
+    db_handler
+        .create_token(&user.id, 1, create("other_token"))
+        .await
+        .unwrap();
+
+    // The name can be reused once the token is deleted
+    db_handler
+        .delete_token(
+            user.id,
+            DeleteToken(DeleteApiTokenRequest {
+                token_id: token_id.to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+    db_handler
+        .create_token(&user.id, 1, create("my_token"))
+        .await
+        .unwrap();
+}
@@ -7,7 +7,7 @@ use aruna_rust_api::api::storage::{
     models::v2::EndpointHostConfig,
     services::v2::{
         ClaimWorkspaceRequest, CreateEndpointRequest, CreateWorkspaceRequest,
-        CreateWorkspaceTemplateRequest,
+        CreateWorkspaceTemplateRequest, UpdateProjectDataClassRequest,
     },
 };
 use aruna_server::{
@@ -23,6 +23,7 @@ use aruna_server::{
     },
     middlelayer::{
         endpoints_request_types::CreateEP,
+        update_request_types::DataClassUpdate,
         workspace_request_types::{CreateTemplate, CreateWorkspace},
     },
 };
@@ -400,5 +401,17 @@ pub async fn claim_workspace() {
     let claimed = Object::get(workspace_id, &client).await.unwrap().unwrap();
     assert_eq!(claimed.data_class, DataClass::PRIVATE);
     assert_eq!(claimed.created_by, user.id);
+    let cached = db_handler.cache.get_object(&workspace_id).unwrap();
+    assert_eq!(cached.object.data_class, DataClass::PRIVATE);
+
+    // Claimed workspaces are regular projects and their dataclass can be changed
+    let updated = db_handler
+        .update_dataclass(DataClassUpdate::Project(UpdateProjectDataClassRequest {
+            project_id: workspace_id.to_string(),
+            data_class: DataClass::PUBLIC as i32,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(updated.object.data_class, DataClass::PUBLIC);
     endpoint_task.abort();
 }
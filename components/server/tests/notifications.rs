@@ -1,4 +1,4 @@
-use aruna_rust_api::api::notification::services::v2::EventVariant;
+use aruna_rust_api::api::notification::services::v2::{EventVariant, Reply};
 use aruna_server::{
     database::{
         crud::CrudDb,
@@ -9,11 +9,16 @@ use aruna_server::{
         enums::ObjectType,
     },
     notification::{
-        handler::{EventHandler, EventType},
-        natsio_handler::NatsIoHandler,
+        handler::{EventHandler, EventStreamHandler, EventType},
+        natsio_handler::{
+            NatsIOEventStreamHandler, NatsIoHandler, DLQ_MAX_AGE, DLQ_MAX_BYTES, DLQ_MAX_MESSAGES,
+            DLQ_STREAM_NAME, MAX_DEAD_LETTERS_PER_BATCH,
+        },
+        utils::calculate_reply_hmac,
     },
 };
 use async_nats::jetstream::consumer::{Config, DeliverPolicy};
+use async_nats::jetstream::stream::DiscardPolicy;
 use diesel_ulid::DieselUlid;
 
 use crate::common::init::init_database;
@@ -276,3 +281,248 @@ async fn resource_notification_test() {
 
     assert_eq!(proj_003_messages.len(), 1);
 }
+
+#[tokio::test]
+async fn dead_letter_reply_test() {
+    // Init Nats.io connection
+    let nats_client = async_nats::connect("0.0.0.0:4222").await.unwrap();
+    let nats_handler = NatsIoHandler::new(nats_client, "ThisIsASecretToken".to_string(), None)
+        .await
+        .unwrap();
+
+    // Init database connection
+    let db = init_database().await;
+    let client = db.get_client().await.unwrap();
+
+    // Create random user
+    let mut user = common::test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+
+    // Create consumer for user and the dead-letter subject
+    let (user_consumer_id, _) = nats_handler
+        .create_event_consumer(EventType::User(user.id.to_string()), DeliverPolicy::All)
+        .await
+        .unwrap();
+    let dlq_consumer = nats_handler
+        .create_dead_letter_consumer(DeliverPolicy::New)
+        .await
+        .unwrap();
+    let all_consumer = nats_handler
+        .create_internal_consumer(
+            DieselUlid::generate(),
+            "AOS.>".to_string(),
+            DeliverPolicy::New,
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Emit two user events
+    for _ in 0..2 {
+        nats_handler
+            .register_user_event(&user, EventVariant::Updated)
+            .await
+            .unwrap();
+    }
+
+    // Give Nats time to process the published messages
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let messages = nats_handler
+        .get_event_consumer_messages(user_consumer_id.to_string(), 10)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 2);
+
+    // Create one valid and one hmac-invalid reply
+    let valid_reply = calculate_reply_hmac(
+        messages[0].reply.as_ref().unwrap(),
+        nats_handler.reply_secret.clone(),
+    );
+    let mut invalid_reply = calculate_reply_hmac(
+        messages[1].reply.as_ref().unwrap(),
+        nats_handler.reply_secret.clone(),
+    );
+    invalid_reply.hmac = "invalid".to_string();
+
+    // Acknowledge mixed batch
+    let dead_letters = nats_handler
+        .acknowledge_from_reply(vec![valid_reply, invalid_reply.clone()])
+        .await
+        .unwrap();
+    assert_eq!(dead_letters, vec![invalid_reply.clone()]);
+
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    // Only the invalid message is still pending acknowledgement
+    let mut user_consumer = nats_handler
+        .get_pull_consumer(user_consumer_id.to_string())
+        .await
+        .unwrap();
+    assert_eq!(user_consumer.info().await.unwrap().num_ack_pending, 1);
+
+    // Invalid reply was routed to the dead-letter subject
+    let dlq_messages = NatsIOEventStreamHandler::new(dlq_consumer)
+        .get_event_consumer_messages(10)
+        .await
+        .unwrap();
+    assert_eq!(dlq_messages.len(), 1);
+    let routed_reply: Reply = serde_json::from_slice(&dlq_messages[0].payload).unwrap();
+    assert_eq!(routed_reply, invalid_reply);
+
+    // Dead letters never reach consumers of the event subjects
+    let event_messages = NatsIOEventStreamHandler::new(all_consumer)
+        .get_event_consumer_messages(10)
+        .await
+        .unwrap();
+    assert!(event_messages
+        .iter()
+        .all(|message| serde_json::from_slice::<Reply>(&message.payload).is_err()));
+}
+
+#[tokio::test]
+async fn dead_letter_limits_test() {
+    // Init Nats.io connection
+    let nats_client = async_nats::connect("0.0.0.0:4222").await.unwrap();
+    let nats_handler =
+        NatsIoHandler::new(nats_client.clone(), "ThisIsASecretToken".to_string(), None)
+            .await
+            .unwrap();
+
+    // Dead letters are retained with limits, discarding the oldest
+    let config = async_nats::jetstream::new(nats_client)
+        .get_stream(DLQ_STREAM_NAME)
+        .await
+        .unwrap()
+        .info()
+        .await
+        .unwrap()
+        .config
+        .clone();
+    assert_eq!(config.max_age, DLQ_MAX_AGE);
+    assert_eq!(config.max_bytes, DLQ_MAX_BYTES);
+    assert_eq!(config.max_messages, DLQ_MAX_MESSAGES);
+    assert_eq!(config.discard, DiscardPolicy::Old);
+
+    // A single batch cannot publish more than the per-batch limit
+    let forged = (0..MAX_DEAD_LETTERS_PER_BATCH + 5)
+        .map(|_| Reply {
+            reply: format!("$JS.ACK.forged.{}", DieselUlid::generate()),
+            salt: "salt".to_string(),
+            hmac: "invalid".to_string(),
+        })
+        .collect::<Vec<_>>();
+    let dead_letters = nats_handler.acknowledge_from_reply(forged).await.unwrap();
+    assert_eq!(dead_letters.len(), MAX_DEAD_LETTERS_PER_BATCH);
+}
+
+#[tokio::test]
+async fn fetch_max_bytes_test() {
+    // Init Nats.io connection
+    let nats_client = async_nats::connect("0.0.0.0:4222").await.unwrap();
+    let nats_handler = NatsIoHandler::new(nats_client, "ThisIsASecretToken".to_string(), None)
+        .await
+        .unwrap();
+
+    // Init database connection
+    let db = init_database().await;
+    let client = db.get_client().await.unwrap();
+
+    // Create random user
+    let mut user = common::test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+
+    // Create consumer for user
+    let (user_consumer_id, _) = nats_handler
+        .create_event_consumer(EventType::User(user.id.to_string()), DeliverPolicy::All)
+        .await
+        .unwrap();
+
+    // Emit five user events
+    for _ in 0..5 {
+        nats_handler
+            .register_user_event(&user, EventVariant::Updated)
+            .await
+            .unwrap();
+    }
+
+    // Give Nats time to process the published messages
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    // Limit batch to roughly the size of a single message
+    let consumer = nats_handler
+        .get_pull_consumer(user_consumer_id.to_string())
+        .await
+        .unwrap();
+    let messages = NatsIOEventStreamHandler::new(consumer)
+        .with_fetch_limits(512, 250)
+        .get_event_consumer_messages(5)
+        .await
+        .unwrap();
+
+    assert!(!messages.is_empty());
+    assert!(messages.len() < 5);
+}
+
+#[tokio::test]
+async fn replay_from_sequence_test() {
+    // Init Nats.io connection
+    let nats_client = async_nats::connect("0.0.0.0:4222").await.unwrap();
+    let nats_handler = NatsIoHandler::new(nats_client, "ThisIsASecretToken".to_string(), None)
+        .await
+        .unwrap();
+
+    // Init database connection
+    let db = init_database().await;
+    let client = db.get_client().await.unwrap();
+
+    // Create random user
+    let mut user = common::test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+
+    // Create consumer for user
+    let (user_consumer_id, _) = nats_handler
+        .create_event_consumer(EventType::User(user.id.to_string()), DeliverPolicy::All)
+        .await
+        .unwrap();
+
+    // Emit four user events
+    for _ in 0..4 {
+        nats_handler
+            .register_user_event(&user, EventVariant::Updated)
+            .await
+            .unwrap();
+    }
+
+    // Give Nats time to process the published messages
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let messages = nats_handler
+        .get_event_consumer_messages(user_consumer_id.to_string(), 10)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 4);
+
+    // Replay from the third message on with a new consumer
+    let midpoint = messages[2].info().unwrap().stream_sequence;
+    let (replay_consumer_id, _) = nats_handler
+        .create_event_consumer(
+            EventType::User(user.id.to_string()),
+            DeliverPolicy::ByStartSequence {
+                start_sequence: midpoint,
+            },
+        )
+        .await
+        .unwrap();
+
+    let replayed = nats_handler
+        .get_event_consumer_messages(replay_consumer_id.to_string(), 10)
+        .await
+        .unwrap();
+    assert_eq!(replayed.len(), 2);
+    assert_eq!(replayed[0].info().unwrap().stream_sequence, midpoint);
+    assert_eq!(
+        replayed[1].info().unwrap().stream_sequence,
+        messages[3].info().unwrap().stream_sequence
+    );
+}
@@ -1,4 +1,5 @@
 use aruna_rust_api::api::storage::models::v2::generic_resource;
+use aruna_server::database::crud::CrudDb;
 use aruna_server::database::dsls::object_dsl::Author;
 use aruna_server::{
     database::{
@@ -6,6 +7,7 @@ use aruna_server::{
         enums::{DataClass, ObjectStatus, ObjectType},
     },
     search::meilisearch_client::{MeilisearchClient, MeilisearchIndexes, ObjectDocument},
+    utils::search_utils::{full_sync_search_index, parse_stop_words, parse_synonym_groups},
 };
 use chrono::NaiveDateTime;
 use diesel_ulid::DieselUlid;
@@ -59,6 +61,14 @@ async fn search_test() {
     assert_eq!(hits.len(), 1);
     assert_eq!(estimated_total, 1);
 
+    // Count only query returns the same total without hits
+    let (count_hits, count_total) = meilisearch_client
+        .query_generic_stuff::<ObjectDocument>("objects", &search_query, "", 0, 0)
+        .await
+        .unwrap();
+    assert!(count_hits.is_empty());
+    assert_eq!(count_total, estimated_total);
+
     // Query some stuff with broken filter
     let mut query_filter = r#"resource_status IN [AVAILABLE, "ERROR"]"#;
     let result = meilisearch_client
@@ -109,6 +119,177 @@ async fn search_test() {
     }
 }
 
+#[tokio::test]
+async fn search_title_ranking_test() {
+    // Create Meilisearch client
+    let meilisearch_client =
+        MeilisearchClient::new("http://localhost:7700", Some("MASTER_KEY")).unwrap();
+    meilisearch_client
+        .get_or_create_index("objects", Some("id"))
+        .await
+        .unwrap();
+
+    // One document matches in its description, the other in its title
+    let mut rng = thread_rng();
+    let token = (0..16)
+        .map(|_| rng.gen_range(b'a'..=b'z') as char)
+        .collect::<String>();
+    let mut description_match = generate_random_object_document();
+    description_match.description = format!("Description mentioning {}", token);
+    let mut title_match = generate_random_object_document();
+    title_match.title = format!("Title mentioning {}", token);
+
+    meilisearch_client
+        .add_or_update_stuff(
+            &[description_match.clone(), title_match.clone()],
+            MeilisearchIndexes::OBJECT,
+        )
+        .await
+        .unwrap()
+        .wait_for_completion(&meilisearch_client.client, None, None)
+        .await
+        .unwrap();
+
+    let (hits, _) = meilisearch_client
+        .query_generic_stuff::<ObjectDocument>("objects", &token, "", 1000, 0)
+        .await
+        .unwrap();
+
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].id, title_match.id);
+    assert_eq!(hits[1].id, description_match.id);
+}
+
+#[tokio::test]
+async fn search_full_sync_test() {
+    // Init
+    let database = common::init::init_database().await;
+    let cache = common::init::init_cache(database.clone(), false).await;
+    let search_client = common::init::init_search_client().await;
+    let client = database.get_client().await.unwrap();
+    let mut user = common::test_utils::new_user(vec![]);
+    user.create(&client).await.unwrap();
+    let mut project =
+        common::test_utils::new_object(user.id, DieselUlid::generate(), ObjectType::PROJECT);
+    project.create(&client).await.unwrap();
+    let search_query = format!("\"{}\"", project.id);
+
+    // Remove document from index
+    search_client
+        .delete_stuff(&[project.id.to_string()], MeilisearchIndexes::OBJECT)
+        .await
+        .unwrap()
+        .wait_for_completion(&search_client.client, None, None)
+        .await
+        .unwrap();
+    let (hits, _) = search_client
+        .query_generic_stuff::<ObjectDocument>("objects", &search_query, "", 1000, 0)
+        .await
+        .unwrap();
+    assert!(hits.is_empty());
+
+    // Full sync restores the document
+    let count = full_sync_search_index(database, cache, search_client.clone())
+        .await
+        .unwrap();
+    assert!(count > 0);
+    let (hits, _) = search_client
+        .query_generic_stuff::<ObjectDocument>("objects", &search_query, "", 1000, 0)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, project.id);
+}
+
+#[tokio::test]
+async fn search_synonyms_test() {
+    // Create Meilisearch client and a dedicated index
+    let meilisearch_client =
+        MeilisearchClient::new("http://localhost:7700", Some("MASTER_KEY")).unwrap();
+    let index_name = create_vocabulary_index(&meilisearch_client).await;
+
+    let (word, synonym) = (random_word(), random_word());
+    let mut document = generate_random_object_document();
+    document.title = format!("Title mentioning {}", word);
+    add_documents(&meilisearch_client, &index_name, &[document.clone()]).await;
+
+    // No match without synonyms
+    let (hits, _) = meilisearch_client
+        .query_generic_stuff::<ObjectDocument>(&index_name, &synonym, "", 1000, 0)
+        .await
+        .unwrap();
+    assert!(hits.is_empty());
+
+    // Synonym matches the document
+    meilisearch_client
+        .set_synonyms(
+            &index_name,
+            &parse_synonym_groups(&format!("{},{}", word, synonym)),
+        )
+        .await
+        .unwrap();
+    let (hits, _) = meilisearch_client
+        .query_generic_stuff::<ObjectDocument>(&index_name, &synonym, "", 1000, 0)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, document.id);
+
+    delete_vocabulary_index(&meilisearch_client, &index_name).await;
+}
+
+#[tokio::test]
+async fn search_stop_words_test() {
+    // Create Meilisearch client and a dedicated index
+    let meilisearch_client =
+        MeilisearchClient::new("http://localhost:7700", Some("MASTER_KEY")).unwrap();
+    let index_name = create_vocabulary_index(&meilisearch_client).await;
+
+    let (word, stop_word) = (random_word(), random_word());
+    let mut document = generate_random_object_document();
+    document.title = format!("Title mentioning {}", word);
+    add_documents(&meilisearch_client, &index_name, &[document.clone()]).await;
+
+    // Unknown words rule out the document. The stop word goes first, as
+    // Meilisearch drops trailing query words until documents match.
+    let search_query = format!("{} {}", stop_word, word);
+    let (hits, _) = meilisearch_client
+        .query_generic_stuff::<ObjectDocument>(&index_name, &search_query, "", 1000, 0)
+        .await
+        .unwrap();
+    assert!(hits.is_empty());
+
+    // Stop words are ignored in queries
+    meilisearch_client
+        .set_stop_words(&index_name, &parse_stop_words(&stop_word))
+        .await
+        .unwrap();
+    let (hits, _) = meilisearch_client
+        .query_generic_stuff::<ObjectDocument>(&index_name, &search_query, "", 1000, 0)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, document.id);
+
+    delete_vocabulary_index(&meilisearch_client, &index_name).await;
+}
+
+#[test]
+fn parse_search_vocabulary_test() {
+    assert_eq!(parse_stop_words(" The, a ,,of"), vec!["the", "a", "of"]);
+    assert_eq!(
+        parse_synonym_groups("genome, genomic;single;rna,RNA-seq,transcriptome"),
+        vec![
+            vec!["genome".to_string(), "genomic".to_string()],
+            vec![
+                "rna".to_string(),
+                "rna-seq".to_string(),
+                "transcriptome".to_string()
+            ],
+        ]
+    );
+}
+
 fn generate_random_object_document() -> ObjectDocument {
     let mut rng = thread_rng();
     let name_parts = vec![
@@ -174,3 +355,46 @@ fn generate_random_object_document() -> ObjectDocument {
         data_license: "AllRightsReserved".to_string(),
     }
 }
+
+fn random_word() -> String {
+    let mut rng = thread_rng();
+    (0..16)
+        .map(|_| rng.gen_range(b'a'..=b'z') as char)
+        .collect()
+}
+
+async fn create_vocabulary_index(meilisearch_client: &MeilisearchClient) -> String {
+    let index_name = format!("vocabulary_{}", DieselUlid::generate());
+    meilisearch_client
+        .get_or_create_index(&index_name, Some("id"))
+        .await
+        .unwrap();
+    index_name
+}
+
+async fn add_documents(
+    meilisearch_client: &MeilisearchClient,
+    index_name: &str,
+    documents: &[ObjectDocument],
+) {
+    meilisearch_client
+        .client
+        .index(index_name)
+        .add_or_replace(documents, Some("id"))
+        .await
+        .unwrap()
+        .wait_for_completion(&meilisearch_client.client, None, None)
+        .await
+        .unwrap();
+}
+
+async fn delete_vocabulary_index(meilisearch_client: &MeilisearchClient, index_name: &str) {
+    meilisearch_client
+        .client
+        .delete_index(index_name)
+        .await
+        .unwrap()
+        .wait_for_completion(&meilisearch_client.client, None, None)
+        .await
+        .unwrap();
+}